@@ -17,6 +17,16 @@ pub fn device_macro_derive(input: TokenStream) -> TokenStream {
             fn get_attribute(&self, name: &str) -> Attribute {
                 self.driver.get_attribute(name)
             }
+
+            fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+                self.driver.try_get_attribute(name)
+            }
+
+            fn reconnect(&mut self, port: &dyn crate::Port) -> Ev3Result<()> {
+                self.driver
+                    .reconnect(port, Self::DRIVER_NAMES)
+                    .map_err(Self::map_error)
+            }
         }
     };
     gen.into()
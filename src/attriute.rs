@@ -2,26 +2,98 @@
 use std::error::Error;
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
-use std::os::unix::fs::PermissionsExt;
+use std::os::unix::fs::{FileExt, PermissionsExt};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::string::String;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{utils::OrErr, Ev3Error, Ev3Result};
+#[cfg(target_os = "linux")]
+use crate::wait::Subscription;
+#[cfg(all(target_os = "linux", feature = "tokio"))]
+use crate::wait::WaitFuture;
 
-/// The root driver path `/sys/class/`.
-const ROOT_PATH: &str = "/sys/class/";
+use crate::{
+    utils::{root_path, OrErr},
+    Ev3Error, Ev3Result,
+};
+
+/// Log target of the attribute file accesses with the `log` feature.
+#[cfg(feature = "log")]
+const LOG_TARGET: &str = "ev3dev_lang_rust::attribute";
+
+/// Logs an access of the attribute file at the `trace` level with the `log` feature.
+/// Expands to nothing without the feature, so the arguments are not even evaluated.
+macro_rules! log_access {
+    ($attribute:expr, $($arg:tt)+) => {
+        #[cfg(feature = "log")]
+        log::trace!(target: LOG_TARGET, "{} {}", format_args!($($arg)+), $attribute.path);
+    };
+}
+
+/// Retryable errno values set by `Attribute::set_retryable_errnos`.
+static RETRYABLE_ERRNOS: RwLock<Option<Vec<i32>>> = RwLock::new(None);
 
 /// A wrapper to a attribute file in the `/sys/class/` directory.
 #[derive(Debug, Clone)]
 pub struct Attribute {
     file: Arc<Mutex<File>>,
+    /// Path of the wrapped file, only used for logging and the dry-run mode.
+    #[cfg(any(feature = "log", feature = "mock"))]
+    path: Arc<str>,
+    /// Writes are recorded, see `mock::set_dry_run`.
+    #[cfg(feature = "mock")]
+    dry_run: bool,
+    /// Writes truncate the file, see `Attribute::new`.
+    #[cfg(feature = "mock")]
+    truncate: bool,
+    /// Value of the last write by `set_if_changed`, `None` if unknown.
+    last_written: Arc<Mutex<Option<String>>>,
+    /// Last value read by `get`, see `set_min_poll_interval`.
+    read_cache: Arc<Mutex<ReadCache>>,
+}
+
+/// Last value read from an attribute file.
+#[derive(Debug, Default)]
+struct ReadCache {
+    /// Reads within this interval after `last_read` return `value`.
+    min_interval: Duration,
+    last_read: Option<Instant>,
+    value: Option<String>,
+    /// Value cached by `get_keyed` together with its key.
+    keyed_value: Option<(usize, String)>,
+    /// Number of writes through this attribute and its clones.
+    write_count: usize,
+}
+
+impl ReadCache {
+    /// Drops the cached values after a write.
+    fn invalidate(&mut self) {
+        self.value = None;
+        self.keyed_value = None;
+        self.write_count = self.write_count.wrapping_add(1);
+    }
 }
 
 impl Attribute {
+    /// Errno values that `get_retry` retries by default:
+    /// `EINTR`, `EAGAIN` and `EBUSY` of a busy system and `ENODEV` right after hotplug.
+    pub const DEFAULT_RETRYABLE_ERRNOS: &'static [i32] =
+        &[libc::EINTR, libc::EAGAIN, libc::EBUSY, libc::ENODEV];
+
     /// Create a new `Attribute` instance for the given path.
+    ///
+    /// In dry-run mode (see `mock::set_dry_run`) the attribute is kept in memory instead.
     pub fn from_path(path: &str) -> Ev3Result<Attribute> {
-        let stat = fs::metadata(&path)?;
+        #[cfg(feature = "mock")]
+        {
+            if let Some(file) = crate::mock::open_dry_run_file(path)? {
+                return Ok(Attribute::new(file, path, true));
+            }
+        }
+
+        let stat = fs::metadata(path)?;
 
         let mode = stat.permissions().mode();
 
@@ -32,21 +104,54 @@ impl Attribute {
         let file = OpenOptions::new()
             .read(readable)
             .write(writeable)
-            .open(&path)?;
+            .open(path)?;
 
-        Ok(Attribute {
-            file: Arc::new(Mutex::new(file)),
-        })
+        Ok(Attribute::new(Arc::new(Mutex::new(file)), path, false))
+    }
+
+    /// Wraps the opened `file` of the given `path`.
+    ///
+    /// Sysfs replaces the whole value on every write. The files of the dry-run mode and of mock devices
+    /// (see `truncate_writes`) are regular files and need to be truncated instead.
+    #[allow(unused_variables)]
+    fn new(file: Arc<Mutex<File>>, path: &str, dry_run: bool) -> Attribute {
+        Attribute {
+            file,
+            #[cfg(any(feature = "log", feature = "mock"))]
+            path: Arc::from(path),
+            #[cfg(feature = "mock")]
+            dry_run,
+            #[cfg(feature = "mock")]
+            truncate: dry_run,
+            last_written: Arc::new(Mutex::new(None)),
+            read_cache: Arc::new(Mutex::new(ReadCache::default())),
+        }
+    }
+
+    /// Truncates the file on every write, for the regular files of a `Driver::with_root`.
+    #[cfg(feature = "mock")]
+    pub(crate) fn truncate_writes(mut self) -> Attribute {
+        self.truncate = true;
+        self
+    }
+
+    /// Records a write in dry-run mode, see `mock::recorded_writes`.
+    #[cfg(feature = "mock")]
+    fn record_write(&self, bytes: &[u8]) {
+        if self.dry_run {
+            crate::mock::record_write(&self.path, bytes);
+        }
     }
 
     /// Create a new `Attribute` instance that wrappes
     /// the file `/sys/class/{class_name}/{name}{attribute_name}`.
+    /// The root path `/sys/class/` can be changed with `Driver::set_root_path`.
     pub fn from_sys_class(
         class_name: &str,
         name: &str,
         attribute_name: &str,
     ) -> Ev3Result<Attribute> {
-        let path = format!("{}{}/{}/{}", ROOT_PATH, class_name, name, attribute_name);
+        let path = format!("{}{}/{}/{}", root_path(), class_name, name, attribute_name);
         Attribute::from_path(&path)
     }
 
@@ -111,22 +216,120 @@ impl Attribute {
         })
     }
 
-    /// Returns the current value of the wrapped file.
+    /// Returns the current value of the wrapped file
+    /// or the cached value if it was read within the minimal poll interval.
     fn get_str(&self) -> Ev3Result<String> {
-        let mut value = String::new();
         let mut file = self.file.lock().unwrap();
+        let mut cache = self.read_cache.lock().unwrap();
+        let now = Instant::now();
+
+        if let (Some(last_read), Some(ref value)) = (cache.last_read, &cache.value) {
+            if now.duration_since(last_read) < cache.min_interval {
+                return Ok(value.clone());
+            }
+        }
+
+        let mut value = String::new();
         file.seek(SeekFrom::Start(0))?;
         file.read_to_string(&mut value)?;
-        Ok(value.trim_end().to_owned())
+        let value = value.trim_end().to_owned();
+        log_access!(self, "read {:?} from", value);
+
+        cache.last_read = Some(now);
+        cache.value = Some(value.clone());
+        Ok(value)
     }
 
-    /// Sets the value of the wrapped file.
+    /// Sets the minimal interval between two reads of the wrapped file.
+    /// Calls of `get` and `get_vec` within this interval after the last read return the cached value.
+    /// A zero interval, the default, reads the file on every call.
+    ///
+    /// The cache is shared by all clones of this attribute. Every write invalidates the cached value.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let value0 = Attribute::from_sys_class("lego-sensor", "sensor0", "value0")?;
+    /// value0.set_min_poll_interval(Duration::from_millis(10));
+    ///
+    /// let first: i32 = value0.get()?;
+    /// // Read within 10ms, the cached value is returned.
+    /// let second: i32 = value0.get()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_min_poll_interval(&self, interval: Duration) {
+        self.read_cache.lock().unwrap().min_interval = interval;
+    }
+
+    /// Returns the minimal interval between two reads of the wrapped file.
+    pub fn get_min_poll_interval(&self) -> Duration {
+        self.read_cache.lock().unwrap().min_interval
+    }
+
+    /// Returns the number of writes through this attribute and its clones.
+    pub(crate) fn write_count(&self) -> usize {
+        self.read_cache.lock().unwrap().write_count
+    }
+
+    /// Returns the value cached for `key`, or reads the file and caches the value for `key`.
+    ///
+    /// This is used for attributes that only change together with another attribute, e.g. the `decimals`
+    /// of a sensor change with its `mode` and are keyed by the `write_count` of the `mode` attribute.
+    pub(crate) fn get_keyed<T>(&self, key: usize) -> Ev3Result<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: Error,
+    {
+        let cached = match self.read_cache.lock().unwrap().keyed_value {
+            Some((cached_key, ref value)) if cached_key == key => Some(value.clone()),
+            _ => None,
+        };
+
+        let value = match cached {
+            Some(value) => value,
+            None => {
+                let value = self.get_str()?;
+                self.read_cache.lock().unwrap().keyed_value = Some((key, value.clone()));
+                value
+            }
+        };
+
+        match value.parse::<T>() {
+            Ok(value) => Ok(value),
+            Err(e) => Err(Ev3Error::InternalError {
+                msg: format!("{}", e),
+            }),
+        }
+    }
+
+    /// Returns the point in time of the last real read by `get` or `get_vec`,
+    /// `None` if the file was not read yet.
+    pub fn last_read_instant(&self) -> Option<Instant> {
+        self.read_cache.lock().unwrap().last_read
+    }
+
+    /// Sets the value of the wrapped file to `value`.
+    /// The bytes of the string are written directly, without the `ToString` conversion of `set`.
+    /// Use this for values that already are strings, e.g. commands in a control loop.
+    /// `set` and `set_str_slice` delegate to this function.
     /// Returns a `Ev3Result::InternalError` if the file is not writable.
-    fn set_str(&self, value: &str) -> Ev3Result<()> {
-        let mut file = self.file.lock().unwrap();
-        file.seek(SeekFrom::Start(0))?;
-        file.write_all(value.as_bytes())?;
-        Ok(())
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let command = Attribute::from_sys_class("tacho-motor", "motor0", "command")?;
+    /// command.set_str("run-forever")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_str(&self, value: &str) -> Ev3Result<()> {
+        self.set_bytes(value.as_bytes())
     }
 
     /// Returns the current value of the wrapped file.
@@ -146,6 +349,116 @@ impl Attribute {
         }
     }
 
+    /// Returns the current value of the wrapped file like `get`, but retries transient os errors.
+    /// The value is read up to `attempts` times, waiting `backoff` between two attempts.
+    ///
+    /// Only `Ev3Error::Os` errors with a retryable errno are retried (see `set_retryable_errnos`),
+    /// all other errors and the error of the last attempt are returned immediately.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let value0 = Attribute::from_sys_class("lego-sensor", "sensor0", "value0")?;
+    /// let value: i32 = value0.get_retry(3, Duration::from_millis(10))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_retry<T>(&self, attempts: u32, backoff: Duration) -> Ev3Result<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: Error,
+    {
+        let mut attempt = 1;
+        loop {
+            match self.get() {
+                Err(ref e) if attempt < attempts && is_retryable(e) => {
+                    attempt += 1;
+                    thread::sleep(backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Overrides the errno values that are retried by `get_retry` for all attributes.
+    /// `None` restores `DEFAULT_RETRYABLE_ERRNOS`.
+    ///
+    /// # Example
+    /// ```
+    /// use ev3dev_lang_rust::Attribute;
+    ///
+    /// // Only retry `EINTR` and `EAGAIN`, give up immediately if the device was removed.
+    /// Attribute::set_retryable_errnos(Some(&[4, 11]));
+    /// # Attribute::set_retryable_errnos(None);
+    /// ```
+    pub fn set_retryable_errnos(errnos: Option<&[i32]>) {
+        *RETRYABLE_ERRNOS.write().unwrap() = errnos.map(|errnos| errnos.to_vec());
+    }
+
+    /// Returns the current value of the wrapped file, bypassing the file cursor.
+    /// The value is parsed to the type `T`.
+    ///
+    /// In contrast to `get` this does not seek the shared file handle but issues a positioned read
+    /// at offset 0 (`pread`). Sysfs regenerates the file content for every read at offset 0,
+    /// so the result always reflects the value at the time of the call.
+    /// This is useful for fast changing files like the `value0` attribute of a sensor.
+    ///
+    /// Returns a `Ev3Result::InternalError` containing the raw file content
+    /// if the current value is not parsable to type `T`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let value0 = Attribute::from_sys_class("lego-sensor", "sensor0", "value0")?;
+    /// let value: i32 = value0.get_uncached()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_uncached<T>(&self) -> Ev3Result<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: Error,
+    {
+        let value = String::from_utf8(self.read_all()?)?;
+        let value = value.trim_end();
+        log_access!(self, "read {:?} from", value);
+        match value.parse::<T>() {
+            Ok(value) => Ok(value),
+            Err(e) => Err(Ev3Error::InternalError {
+                msg: format!("{} (raw value: `{}`)", e, value),
+            }),
+        }
+    }
+
+    /// Returns the raw content of the wrapped file, bypassing the read cache.
+    /// This can be used for binary attributes like the `bin_data` attribute of a sensor.
+    pub fn get_bytes(&self) -> Ev3Result<Vec<u8>> {
+        let bytes = self.read_all()?;
+        log_access!(self, "read {:02x?} from", bytes);
+        Ok(bytes)
+    }
+
+    /// Reads up to 4096 bytes, the page size of sysfs attributes, from the start of the wrapped file
+    /// with positioned reads.
+    fn read_all(&self) -> Ev3Result<Vec<u8>> {
+        let mut buffer = [0u8; 4096];
+        let mut length = 0;
+        let file = self.file.lock().unwrap();
+        while length < buffer.len() {
+            let count = file.read_at(&mut buffer[length..], length as u64)?;
+            if count == 0 {
+                break;
+            }
+            length += count;
+        }
+        Ok(buffer[..length].to_vec())
+    }
+
     /// Sets the value of the wrapped file.
     /// The value is parsed from the type `T`.
     /// Returns a `Ev3Result::InternalError` if the file is not writable.
@@ -156,16 +469,159 @@ impl Attribute {
         self.set_str(&value.to_string())
     }
 
+    /// Sets the value of the wrapped file if it differs from the last value written by this function.
+    /// The value is parsed from the type `T`.
+    /// Returns `true` if the value was written, `false` if the write was skipped.
+    ///
+    /// The comparison is done against the last value written through this attribute (and its clones),
+    /// not against the current file content. Every other write (`set`, `set_str_slice`) invalidates
+    /// the cached value, so the next call of this function always writes.
+    /// Returns a `Ev3Result::InternalError` if the file is not writable.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let speed_sp = Attribute::from_sys_class("tacho-motor", "motor0", "speed_sp")?;
+    ///
+    /// assert!(speed_sp.set_if_changed(500)?);
+    /// // The value is unchanged, the write is skipped.
+    /// assert!(!speed_sp.set_if_changed(500)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_if_changed<T>(&self, value: T) -> Ev3Result<bool>
+    where
+        T: std::string::ToString,
+    {
+        let value = value.to_string();
+
+        let mut file = self.file.lock().unwrap();
+        let mut last_written = self.last_written.lock().unwrap();
+        if last_written.as_ref() == Some(&value) {
+            return Ok(false);
+        }
+
+        *last_written = None;
+        self.read_cache.lock().unwrap().invalidate();
+        log_access!(self, "write {:?} to", value);
+        #[cfg(feature = "mock")]
+        self.record_write(value.as_bytes());
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(value.as_bytes())?;
+        #[cfg(feature = "mock")]
+        if self.truncate {
+            file.set_len(value.len() as u64)?;
+        }
+        *last_written = Some(value);
+
+        Ok(true)
+    }
+
+    /// Sets the value of the wrapped file like `set` and reads it back, bypassing the read cache.
+    /// Returns the value that is effective after the write, e.g. a `speed_sp` clamped to `max_speed` by the kernel.
+    ///
+    /// Returns a `Ev3Result::InternalError` if the file is not writable
+    /// or if the value read back is not parsable to type `T`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let speed_sp = Attribute::from_sys_class("tacho-motor", "motor0", "speed_sp")?;
+    ///
+    /// let speed: i32 = speed_sp.set_and_verify(5000)?;
+    /// if speed != 5000 {
+    ///     println!("speed_sp was clamped to {}", speed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_and_verify<T>(&self, value: T) -> Ev3Result<T>
+    where
+        T: std::string::ToString + std::str::FromStr,
+        <T as std::str::FromStr>::Err: Error,
+    {
+        self.set(value)?;
+        self.get_uncached()
+    }
+
     #[inline]
-    /// Sets the value of the wrapped file.
+    /// Sets the value of the wrapped file, same as `set_str`.
     /// This function skips the string parsing of the `self.set<T>()` function.
     /// Returns a `Ev3Result::InternalError` if the file is not writable.
     pub fn set_str_slice(&self, value: &str) -> Ev3Result<()> {
         self.set_str(value)
     }
 
+    /// Writes the raw `bytes` to the wrapped file with a single write.
+    /// This can be used for binary attributes like the `bin_data` attribute of a sensor.
+    /// Returns a `Ev3Result::InternalError` if the file is not writable.
+    pub fn set_bytes(&self, bytes: &[u8]) -> Ev3Result<()> {
+        let mut file = self.file.lock().unwrap();
+        *self.last_written.lock().unwrap() = None;
+        self.read_cache.lock().unwrap().invalidate();
+        log_access!(self, "write {:?} to", String::from_utf8_lossy(bytes));
+        #[cfg(feature = "mock")]
+        self.record_write(bytes);
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(bytes)?;
+        #[cfg(feature = "mock")]
+        if self.truncate {
+            file.set_len(bytes.len() as u64)?;
+        }
+        Ok(())
+    }
+
+    /// Reads raw bytes at the given `offset` of the wrapped file into `buffer` with a single positioned read.
+    /// This can be used for binary attributes like the `direct` attribute of an I2C sensor,
+    /// where the offset selects the register. Returns the number of bytes read.
+    pub fn read_bytes_at(&self, offset: u64, buffer: &mut [u8]) -> Ev3Result<usize> {
+        let file = self.file.lock().unwrap();
+        let count = file.read_at(buffer, offset)?;
+        log_access!(
+            self,
+            "read {:02x?} at offset {} from",
+            &buffer[..count],
+            offset
+        );
+        Ok(count)
+    }
+
+    /// Writes the raw `bytes` at the given `offset` of the wrapped file with a single positioned write,
+    /// see `read_bytes_at`.
+    /// Returns a `Ev3Result::InternalError` if the file is not writable.
+    pub fn write_bytes_at(&self, offset: u64, bytes: &[u8]) -> Ev3Result<()> {
+        let file = self.file.lock().unwrap();
+        *self.last_written.lock().unwrap() = None;
+        self.read_cache.lock().unwrap().invalidate();
+        log_access!(self, "write {:02x?} at offset {} to", bytes, offset);
+        #[cfg(feature = "mock")]
+        self.record_write(bytes);
+        file.write_all_at(bytes, offset)?;
+        Ok(())
+    }
+
     /// Returns a string vector representation of the wrapped file.
-    /// The file value is splitet at whitespaces.
+    /// The file value is split at whitespaces, so trailing newlines are dropped
+    /// and an empty file returns an empty vector.
+    /// This is used for list attributes like `modes`, `commands` and `stop_actions`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let commands = Attribute::from_sys_class("tacho-motor", "motor0", "commands")?;
+    ///
+    /// for command in commands.get_vec()? {
+    ///     println!("Supported command: {}", command);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn get_vec(&self) -> Ev3Result<Vec<String>> {
         let value = self.get_str()?;
         let vec = value
@@ -175,8 +631,95 @@ impl Attribute {
         Ok(vec)
     }
 
+    /// Wait asynchronously until the condition `cond` is `true` or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// The condition is checked when the file has changed, just like `wait::wait`.
+    ///
+    /// The returned future must be polled within a tokio runtime.
+    /// It resolves to `Ev3Error::Timeout` if the `timeout` is reached.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let state = Attribute::from_sys_class("tacho-motor", "motor0", "state")?;
+    /// let stopped = state.clone();
+    /// let cond = move || {
+    ///     stopped
+    ///         .get_vec()
+    ///         .map(|states| !states.iter().any(|s| s == "running"))
+    ///         .unwrap_or(false)
+    /// };
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread()
+    ///     .enable_all()
+    ///     .build()?;
+    /// runtime.block_on(state.wait_async(cond, Some(Duration::from_secs(5))))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(target_os = "linux", feature = "tokio"))]
+    pub fn wait_async<F>(&self, cond: F, timeout: Option<Duration>) -> WaitFuture<F>
+    where
+        F: Fn() -> bool + Unpin,
+    {
+        WaitFuture::new(self.clone(), cond, timeout)
+    }
+
+    /// Calls `f` on a background thread whenever the parsed value of the attribute changes.
+    /// The initial value is not reported.
+    ///
+    /// The thread waits for changes of the file like `wait::wait` and exits if the attribute
+    /// can not be read anymore, e.g. because the device was disconnected.
+    /// Dropping the returned `Subscription` stops the thread.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Attribute;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let value0 = Attribute::from_sys_class("lego-sensor", "sensor0", "value0")?;
+    ///
+    /// let subscription = value0.on_change(|value: i32| {
+    ///     println!("New value: {}", value);
+    /// });
+    ///
+    /// thread::sleep(Duration::from_secs(10));
+    /// drop(subscription);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn on_change<T, F>(&self, f: F) -> Subscription
+    where
+        T: std::str::FromStr + PartialEq + Clone + Send + 'static,
+        <T as std::str::FromStr>::Err: Error,
+        F: FnMut(T) + Send + 'static,
+    {
+        Subscription::spawn(self.clone(), f)
+    }
+
     /// Returns a C pointer to the wrapped file.
+    ///
+    /// The file descriptor can be registered in an external `epoll`/`mio` event loop
+    /// (see `wait::Epoll`). It is owned by this attribute and all its clones:
+    /// the caller must not close it and must not use it after the last clone was dropped.
     pub fn get_raw_fd(&self) -> RawFd {
         self.file.lock().unwrap().as_raw_fd()
     }
 }
+
+/// Returns `true` if `error` is an `Ev3Error::Os` with a retryable errno.
+fn is_retryable(error: &Ev3Error) -> bool {
+    match error.raw_os_error() {
+        Some(errno) => match *RETRYABLE_ERRNOS.read().unwrap() {
+            Some(ref errnos) => errnos.contains(&errno),
+            None => Attribute::DEFAULT_RETRYABLE_ERRNOS.contains(&errno),
+        },
+        None => false,
+    }
+}
@@ -25,9 +25,15 @@ use std::collections::HashSet;
 use std::fmt;
 use std::fs::File;
 use std::os::unix::io::AsRawFd;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::RawFd;
 use std::rc::Rc;
+#[cfg(target_os = "linux")]
+use std::time::Duration;
 
-use crate::Ev3Result;
+#[cfg(target_os = "linux")]
+use crate::wait;
+use crate::{Ev3Result, Platform};
 
 const KEY_BUF_LEN: usize = 96;
 const EVIOCGKEY: u32 = 2_153_792_792;
@@ -61,6 +67,7 @@ struct ButtonFileHandler {
     file_map: HashMap<String, FileMapEntry>,
     button_map: HashMap<String, ButtonMapEntry>,
     pressed_buttons: HashSet<String>,
+    previous_pressed_buttons: HashSet<String>,
 }
 
 impl ButtonFileHandler {
@@ -70,6 +77,7 @@ impl ButtonFileHandler {
             file_map: HashMap::new(),
             button_map: HashMap::new(),
             pressed_buttons: HashSet::new(),
+            previous_pressed_buttons: HashSet::new(),
         }
     }
 
@@ -98,11 +106,44 @@ impl ButtonFileHandler {
         self.pressed_buttons.clone()
     }
 
+    /// Returns the buttons that are pressed now, but were not pressed at the previous `process()` call.
+    fn get_just_pressed_buttons(&self) -> HashSet<String> {
+        self.pressed_buttons
+            .difference(&self.previous_pressed_buttons)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the buttons that were pressed at the previous `process()` call, but are released now.
+    fn get_just_released_buttons(&self) -> HashSet<String> {
+        self.previous_pressed_buttons
+            .difference(&self.pressed_buttons)
+            .cloned()
+            .collect()
+    }
+
     /// Check if a button is pressed.
     fn get_button_state(&self, name: &str) -> bool {
         self.pressed_buttons.contains(name)
     }
 
+    /// Returns the raw file descriptor of the input device of a button.
+    #[cfg(target_os = "linux")]
+    fn get_button_fd(&self, name: &str) -> Option<RawFd> {
+        self.button_map
+            .get(name)
+            .map(|button| self.file_map[&button.file_name].file.as_raw_fd())
+    }
+
+    /// Returns the raw file descriptors of all input devices.
+    #[cfg(all(feature = "screen", target_os = "linux"))]
+    fn get_fds(&self) -> Vec<RawFd> {
+        self.file_map
+            .values()
+            .map(|entry| entry.file.as_raw_fd())
+            .collect()
+    }
+
     /// Check for currenly pressed buttons. If the new state differs from the
     /// old state, call the appropriate button event handlers.
     fn process(&mut self) {
@@ -116,6 +157,10 @@ impl ButtonFileHandler {
             }
         }
 
+        std::mem::swap(
+            &mut self.pressed_buttons,
+            &mut self.previous_pressed_buttons,
+        );
         self.pressed_buttons.clear();
 
         for (
@@ -165,20 +210,14 @@ pub struct Ev3Button {
 }
 
 impl Ev3Button {
-    /// Ev3 brick button handler. Opens the corresponding `/dev/input` file handlers.
+    /// Ev3 brick button handler. Opens the `/dev/input` file handlers of the detected platform, see `Platform::detect`.
+    /// The PiStorms only has the `enter` button, the BrickPi3 has no buttons.
     pub fn new() -> Ev3Result<Self> {
         let mut handler = ButtonFileHandler::new();
 
-        handler.add_button("up", "/dev/input/by-path/platform-gpio_keys-event", 103)?;
-        handler.add_button("down", "/dev/input/by-path/platform-gpio_keys-event", 108)?;
-        handler.add_button("left", "/dev/input/by-path/platform-gpio_keys-event", 105)?;
-        handler.add_button("right", "/dev/input/by-path/platform-gpio_keys-event", 106)?;
-        handler.add_button("enter", "/dev/input/by-path/platform-gpio_keys-event", 28)?;
-        handler.add_button(
-            "backspace",
-            "/dev/input/by-path/platform-gpio_keys-event",
-            14,
-        )?;
+        for &(name, file_name, key_code) in Platform::detect().buttons() {
+            handler.add_button(name, file_name, key_code)?;
+        }
 
         Ok(Self {
             button_handler: Rc::new(RefCell::new(handler)),
@@ -196,6 +235,63 @@ impl Ev3Button {
         self.button_handler.borrow().get_pressed_buttons()
     }
 
+    /// Get all buttons by name that were pressed since the previous `process()` call.
+    /// Buttons that are held down over multiple `process()` calls are only reported once.
+    pub fn get_just_pressed_buttons(&self) -> HashSet<String> {
+        self.button_handler.borrow().get_just_pressed_buttons()
+    }
+
+    /// Get all buttons by name that were released since the previous `process()` call.
+    pub fn get_just_released_buttons(&self) -> HashSet<String> {
+        self.button_handler.borrow().get_just_released_buttons()
+    }
+
+    /// Check if the button `name` (e.g. `"up"` or `"enter"`) is pressed.
+    pub fn is_pressed(&self, name: &str) -> bool {
+        self.button_handler.borrow().get_button_state(name)
+    }
+
+    /// Wait until the button `name` is pressed or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// Returns immediately if the button is already pressed.
+    ///
+    /// The button state is updated by `process()` whenever the input device reports a new event.
+    /// Returns `false` if the `timeout` is reached or the button does not exist.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Ev3Button;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let button = Ev3Button::new()?;
+    ///
+    /// if button.wait_for_press("enter", Some(Duration::from_secs(10))) {
+    ///     println!("Enter pressed");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn wait_for_press(&self, name: &str, timeout: Option<Duration>) -> bool {
+        let fd = match self.button_handler.borrow().get_button_fd(name) {
+            Some(fd) => fd,
+            None => return false,
+        };
+
+        let cond = || {
+            self.process();
+            self.is_pressed(name)
+        };
+        wait::wait_input(fd, cond, timeout)
+    }
+
+    /// Returns the raw file descriptors of the input devices of all buttons.
+    #[cfg(all(feature = "screen", target_os = "linux"))]
+    pub(crate) fn get_fds(&self) -> Vec<RawFd> {
+        self.button_handler.borrow().get_fds()
+    }
+
     /// Check if 'up' button is pressed.
     pub fn is_up(&self) -> bool {
         self.button_handler.borrow().get_button_state("up")
@@ -1,13 +1,77 @@
-use crate::{Attribute, Ev3Result};
+use crate::{
+    utils::{ADDRESS_ATTRIBUTE, DRIVER_NAME_ATTRIBUTE},
+    Attribute, Ev3Error, Ev3Result, Port,
+};
 
-/// The ev3dev device base trait
+/// The ev3dev device base trait, implemented by all motors and sensors.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Device;
+/// use ev3dev_lang_rust::motors::LargeMotor;
+/// use ev3dev_lang_rust::sensors::TouchSensor;
+///
+/// fn describe(device: &dyn Device) -> ev3dev_lang_rust::Ev3Result<String> {
+///     Ok(format!("{} on {}", device.get_driver_name()?, device.get_address()?))
+/// }
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// println!("{}", describe(&LargeMotor::find()?)?);
+/// println!("{}", describe(&TouchSensor::find()?)?);
+/// # Ok(())
+/// # }
+/// ```
 pub trait Device {
     /// Returns the attribute wrapper for an attribute name.
     fn get_attribute(&self, name: &str) -> Attribute;
 
+    /// Returns the attribute wrapper for an attribute name.
+    /// In contrast to `get_attribute` this returns an error if the device does not provide the attribute.
+    ///
+    /// The default implementation can not detect missing attributes and returns `get_attribute`.
+    /// Devices derived with `#[derive(Device)]` check the attribute file.
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        Ok(self.get_attribute(name))
+    }
+
+    /// Re-resolves the sysfs name of the device at the `port`, e.g. after a sensor was unplugged and plugged in again.
+    /// The sysfs name may change on a replug, e.g. from `sensor0` to `sensor1`.
+    ///
+    /// All cached attributes and constants of this handle are dropped. Clones of this handle keep the old name.
+    /// Returns `Ev3Error::NotConnected` and keeps the current name if no device is connected to the `port`
+    /// or the connected device has another driver, e.g. a medium motor was plugged in instead of a large motor.
+    ///
+    /// The default implementation returns `Ev3Error::InternalError`, because the device can not be re-resolved.
+    /// Devices derived with `#[derive(Device)]` look up the device with `Driver::reconnect` and their `DRIVER_NAMES`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Device;
+    /// use ev3dev_lang_rust::sensors::{SensorPort, TouchSensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let mut sensor = TouchSensor::get(SensorPort::In1)?;
+    ///
+    /// loop {
+    ///     match sensor.get_pressed_state() {
+    ///         Ok(pressed) => println!("Pressed: {}", pressed),
+    ///         Err(_) => sensor.reconnect(&SensorPort::In1)?,
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    fn reconnect(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        Err(Ev3Error::InternalError {
+            msg: format!(
+                "The device at `{}` does not support reconnecting",
+                port.address()
+            ),
+        })
+    }
+
     /// Returns the name of the port that the motor is connected to.
     fn get_address(&self) -> Ev3Result<String> {
-        self.get_attribute("address").get()
+        self.get_attribute(ADDRESS_ATTRIBUTE).get()
     }
 
     /// Sends a command to the device controller.
@@ -22,6 +86,15 @@ pub trait Device {
 
     /// Returns the name of the driver that provides this device.
     fn get_driver_name(&self) -> Ev3Result<String> {
-        self.get_attribute("driver_name").get()
+        self.get_attribute(DRIVER_NAME_ATTRIBUTE).get()
+    }
+
+    /// Returns the firmware version of the device.
+    /// Currently only NXT/I2C sensors support this.
+    ///
+    /// Returns `Ev3Error::InternalError` naming the attribute if the device has no `fw_version` attribute.
+    /// Other errors, e.g. missing permissions, are returned as `Ev3Error::Os`.
+    fn get_fw_version(&self) -> Ev3Result<String> {
+        self.try_get_attribute("fw_version")?.get()
     }
 }
@@ -0,0 +1,151 @@
+//! Watcher for connected and disconnected devices of a driver class.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use crate::{
+    utils::{root_path, ADDRESS_ATTRIBUTE},
+    Attribute, Ev3Result,
+};
+
+/// A device was connected to or disconnected from a port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceEvent {
+    /// A new device appeared.
+    Connected {
+        /// Sysfs name of the device, e.g. `sensor0`.
+        name: String,
+        /// Address of the port, e.g. `ev3-ports:in1`.
+        port: String,
+    },
+    /// A device was removed.
+    Disconnected {
+        /// Sysfs name of the device, e.g. `sensor0`.
+        name: String,
+        /// Address of the port the device was connected to.
+        port: String,
+    },
+}
+
+/// Watches the devices in `/sys/class/{class_name}` and reports connected and disconnected devices.
+///
+/// Sysfs class directories do not reliably report changes with `epoll` or `inotify` across ev3dev kernels,
+/// so the directory is rescanned on every `poll`.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::{DeviceEvent, DeviceWatcher};
+/// use ev3dev_lang_rust::motors::LargeMotor;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::find()?;
+/// motor.run_forever()?;
+///
+/// let mut watcher = DeviceWatcher::new("lego-sensor")?;
+/// watcher.watch(Duration::from_millis(100), |event| match *event {
+///     DeviceEvent::Disconnected { ref port, .. } => {
+///         println!("Sensor on port {} was removed!", port);
+///         motor.stop().ok();
+///         false
+///     }
+///     DeviceEvent::Connected { .. } => true,
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeviceWatcher {
+    class_name: String,
+    /// Addresses of the known devices by their sysfs name.
+    devices: HashMap<String, String>,
+}
+
+impl DeviceWatcher {
+    /// Create a new watcher for the driver class `class_name`, e.g. `lego-sensor` or `tacho-motor`.
+    /// The currently connected devices are known, only later changes are reported.
+    pub fn new(class_name: &str) -> Ev3Result<DeviceWatcher> {
+        Ok(DeviceWatcher {
+            class_name: class_name.to_owned(),
+            devices: scan(class_name)?,
+        })
+    }
+
+    /// Returns the sysfs names and port addresses of the currently known devices.
+    pub fn get_devices(&self) -> &HashMap<String, String> {
+        &self.devices
+    }
+
+    /// Rescans the class directory and returns all changes since the last scan.
+    /// Disconnected devices are reported before connected ones, so a replaced device yields both events.
+    pub fn poll(&mut self) -> Ev3Result<Vec<DeviceEvent>> {
+        let devices = scan(&self.class_name)?;
+
+        let mut events = Vec::new();
+        for (name, port) in &self.devices {
+            if devices.get(name) != Some(port) {
+                events.push(DeviceEvent::Disconnected {
+                    name: name.clone(),
+                    port: port.clone(),
+                });
+            }
+        }
+        for (name, port) in &devices {
+            if self.devices.get(name) != Some(port) {
+                events.push(DeviceEvent::Connected {
+                    name: name.clone(),
+                    port: port.clone(),
+                });
+            }
+        }
+
+        self.devices = devices;
+        Ok(events)
+    }
+
+    /// Rescans the class directory every `interval` and invokes the `callback` for every change.
+    /// Blocks until the `callback` returns `false` or a scan fails.
+    pub fn watch<F>(&mut self, interval: Duration, mut callback: F) -> Ev3Result<()>
+    where
+        F: FnMut(&DeviceEvent) -> bool,
+    {
+        loop {
+            for event in self.poll()? {
+                if !callback(&event) {
+                    return Ok(());
+                }
+            }
+            thread::sleep(interval);
+        }
+    }
+}
+
+/// Returns the addresses of all devices of the `class_name` by their sysfs name.
+/// Devices that disappear during the scan are skipped, a missing class directory has no devices.
+fn scan(class_name: &str) -> Ev3Result<HashMap<String, String>> {
+    let paths = match fs::read_dir(format!("{}{}", root_path(), class_name)) {
+        Ok(paths) => paths,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut devices = HashMap::new();
+    for path in paths {
+        let file_name = path?.file_name();
+        let name = match file_name.to_str() {
+            Some(name) => name,
+            None => continue,
+        };
+
+        let address = Attribute::from_sys_class(class_name, name, ADDRESS_ATTRIBUTE)
+            .and_then(|address| address.get::<String>());
+        if let Ok(address) = address {
+            devices.insert(name.to_owned(), address);
+        }
+    }
+
+    Ok(devices)
+}
@@ -0,0 +1,86 @@
+//! Diagnostic listing of all connected motors and sensors.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::devices;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let devices = devices::scan()?;
+//! println!("{}", devices::format_table(&devices));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use crate::{
+    driver::{class_device_names, read_device_attribute},
+    utils::{ADDRESS_ATTRIBUTE, DRIVER_NAME_ATTRIBUTE},
+    Ev3Result,
+};
+
+/// Driver classes that are listed by `scan`.
+pub const DEVICE_CLASSES: &[&str] = &["tacho-motor", "dc-motor", "servo-motor", "lego-sensor"];
+
+/// Description of a connected device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// Driver class, e.g. `lego-sensor`.
+    pub class_name: String,
+    /// Sysfs name, e.g. `sensor0`.
+    pub name: String,
+    /// Address of the port, e.g. `ev3-ports:in1`.
+    pub address: String,
+    /// Name of the driver, e.g. `lego-ev3-color`.
+    pub driver_name: String,
+}
+
+impl fmt::Display for DeviceInfo {
+    /// Formats the device as a row of the table of `format_table`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:<12} {:<10} {:<20} {}",
+            self.class_name, self.name, self.address, self.driver_name
+        )
+    }
+}
+
+/// Returns all devices of the motor and sensor classes (see `DEVICE_CLASSES`), ordered by class and name.
+/// Missing class directories and devices that are removed during the scan are skipped.
+pub fn scan() -> Ev3Result<Vec<DeviceInfo>> {
+    let mut devices = Vec::new();
+
+    for class_name in DEVICE_CLASSES {
+        let mut names = class_device_names(class_name)?;
+        names.sort();
+
+        for name in names {
+            let address = read_device_attribute(class_name, &name, ADDRESS_ATTRIBUTE)?;
+            let driver_name = read_device_attribute(class_name, &name, DRIVER_NAME_ATTRIBUTE)?;
+            if let (Some(address), Some(driver_name)) = (address, driver_name) {
+                devices.push(DeviceInfo {
+                    class_name: (*class_name).to_owned(),
+                    name,
+                    address,
+                    driver_name,
+                });
+            }
+        }
+    }
+
+    Ok(devices)
+}
+
+/// Formats the `devices` as a table with a header row, one device per line.
+pub fn format_table(devices: &[DeviceInfo]) -> String {
+    let mut table = format!(
+        "{:<12} {:<10} {:<20} {}",
+        "CLASS", "NAME", "ADDRESS", "DRIVER"
+    );
+    for device in devices {
+        table.push('\n');
+        table.push_str(&device.to_string());
+    }
+    table
+}
@@ -5,12 +5,21 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::{self, Debug};
 use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::string::String;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{utils::OrErr, Attribute, Ev3Error, Ev3Result, Port};
+use crate::{
+    motors::MotorPort,
+    sensors::SensorPort,
+    utils::{self, root_path, OrErr, ADDRESS_ATTRIBUTE, DRIVER_NAME_ATTRIBUTE},
+    Attribute, BrickPi3Port, Ev3Error, Ev3Result, Port,
+};
 
-/// The root driver path `/sys/class/`.
-const ROOT_PATH: &str = "/sys/class/";
+/// Interval between two directory scans while waiting for a device.
+const RESCAN_INTERVAL: Duration = Duration::from_millis(50);
 
 /// Helper struct that manages attributes.
 /// It creates an `Attribute` instance if it does not exists or uses a cached one.
@@ -19,22 +28,120 @@ pub struct Driver {
     class_name: String,
     name: String,
     attributes: RefCell<HashMap<String, Attribute>>,
+    /// Replaces `/sys/class/` for the attributes of this driver.
+    /// Boxed to keep the size of `Driver` and all devices unchanged.
+    root_path: Option<Box<PathBuf>>,
+    /// The attributes are regular files of a mock device, see `with_root`.
+    #[cfg(feature = "mock")]
+    mock_root: bool,
 }
 
 impl Driver {
     /// Returns a new `Driver`.
     /// All attributes created by this driver will use the path `/sys/class/{class_name}/{name}`.
+    /// See `set_root_path` to change the root path `/sys/class/`.
     pub fn new(class_name: &str, name: &str) -> Driver {
         Driver {
             class_name: class_name.to_owned(),
             name: name.to_owned(),
             attributes: RefCell::new(HashMap::new()),
+            root_path: None,
+            #[cfg(feature = "mock")]
+            mock_root: false,
         }
     }
 
+    /// Overrides the root driver path `/sys/class/` for all devices and attributes created afterwards,
+    /// e.g. for containers that mount sysfs elsewhere. `None` restores the default lookup.
+    ///
+    /// The root path is determined in the following order:
+    /// 1. The path set by this function
+    /// 2. The `EV3DEV_SYS_ROOT` environment variable, read once on first use
+    /// 3. `/sys/class/`
+    ///
+    /// A `Driver::with_root` always uses its own root path.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Driver;
+    /// use ev3dev_lang_rust::motors::LargeMotor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// Driver::set_root_path(Some("/host/sys/class"));
+    ///
+    /// let motor = LargeMotor::find()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_root_path(path: Option<&str>) {
+        utils::set_root_path(path);
+    }
+
+    /// Returns the current root driver path with a trailing `/`, `/sys/class/` by default.
+    pub fn get_root_path() -> String {
+        root_path()
+    }
+
+    /// Returns a new `Driver` that reads and writes its attributes in the directory `{root}/{class_name}/{name}`
+    /// instead of `/sys/class/{class_name}/{name}`.
+    ///
+    /// In contrast to `set_root_path` this only affects this driver,
+    /// e.g. for custom drivers that live outside of `/sys/class/`.
+    /// Lookups like `reconnect` still search the global root path.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Driver;
+    /// use std::path::Path;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let driver = Driver::new_with_root_class(Path::new("/sys/bus/iio/devices"), "", "iio:device0");
+    /// let name: String = driver.try_get_attribute("name")?.get()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_with_root_class(root: &Path, class_name: &str, name: &str) -> Driver {
+        Driver {
+            root_path: Some(Box::new(root.to_owned())),
+            ..Driver::new(class_name, name)
+        }
+    }
+
+    /// Returns a new `Driver` with the given `root`, see `new_with_root_class`.
+    /// This can be used together with `mock::MockDevice` to test robot code without an ev3 brick.
+    #[cfg(feature = "mock")]
+    pub fn with_root(root: &Path, class_name: &str, name: &str) -> Driver {
+        Driver {
+            mock_root: true,
+            ..Driver::new_with_root_class(root, class_name, name)
+        }
+    }
+
+    /// Creates the `Attribute` wrapper for the given `attribute_name`.
+    fn open_attribute(&self, attribute_name: &str) -> Ev3Result<Attribute> {
+        if let Some(ref root_path) = self.root_path {
+            let path = root_path
+                .join(&self.class_name)
+                .join(&self.name)
+                .join(attribute_name);
+            let attribute = Attribute::from_path(path.to_str().or_err()?)?;
+            #[cfg(feature = "mock")]
+            if self.mock_root {
+                return Ok(attribute.truncate_writes());
+            }
+            return Ok(attribute);
+        }
+
+        Attribute::from_sys_class(self.class_name.as_ref(), self.name.as_ref(), attribute_name)
+    }
+
     /// Returns the name of the device with the given `class_name`, `driver_name` and at the given `port`.
     ///
-    /// Returns `Ev3Error::NotFound` if no such device exists.
+    /// The address has to end with the port at a `:` boundary, so `in1` matches `ev3-ports:in1`,
+    /// but neither `ev3-ports:in10` nor a multiplexer channel like `ev3-ports:in1:i2c80`.
+    /// Use `find_name_by_port_and_channel` for multiplexer channels.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such device exists.
     pub fn find_name_by_port_and_driver(
         class_name: &str,
         port: &dyn Port,
@@ -42,20 +149,74 @@ impl Driver {
     ) -> Ev3Result<String> {
         let port_address = port.address();
 
-        let paths = fs::read_dir(format!("{}{}", ROOT_PATH, class_name))?;
+        for name in class_device_names(class_name)? {
+            let address = match read_device_attribute(class_name, &name, ADDRESS_ATTRIBUTE)? {
+                Some(address) => address,
+                None => continue,
+            };
 
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?;
+            if address_matches(&address, &port_address)
+                && driver_matches(class_name, &name, driver_name_vec)?
+            {
+                return Ok(name);
+            }
+        }
 
-            let address = Attribute::from_sys_class(class_name, name, "address")?;
+        Err(Ev3Error::NotConnected {
+            device: format!("{:?}", driver_name_vec),
+            port: Some(port.address()),
+        })
+    }
 
-            if address.get::<String>()?.contains(&port_address) {
-                let driver = Attribute::from_sys_class(class_name, name, "driver_name")?;
-                let driver_name = driver.get::<String>()?;
-                if driver_name_vec.iter().any(|n| &driver_name == n) {
-                    return Ok(name.to_owned());
-                }
+    /// Returns the name of the device with the given `class_name` at the given `port`, regardless of its driver.
+    ///
+    /// The address is matched like in `find_name_by_port_and_driver`.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such device exists.
+    pub fn find_name_by_port(class_name: &str, port: &dyn Port) -> Ev3Result<String> {
+        let port_address = port.address();
+
+        for name in class_device_names(class_name)? {
+            let address = match read_device_attribute(class_name, &name, ADDRESS_ATTRIBUTE)? {
+                Some(address) => address,
+                None => continue,
+            };
+
+            if address_matches(&address, &port_address) {
+                return Ok(name);
+            }
+        }
+
+        Err(Ev3Error::NotConnected {
+            device: class_name.to_owned(),
+            port: Some(port.address()),
+        })
+    }
+
+    /// Returns the name of the first device with the given `class_name`, `driver_name`
+    /// whose address contains the given `port`.
+    ///
+    /// This is the substring matching used by `find_name_by_port_and_driver` in earlier versions.
+    /// It also matches devices at `ev3-ports:in10` or `ev3-ports:in1:i2c80` for the port `in1`.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such device exists.
+    pub fn find_name_by_port_prefix(
+        class_name: &str,
+        port: &dyn Port,
+        driver_name_vec: &[&str],
+    ) -> Ev3Result<String> {
+        let port_address = port.address();
+
+        for name in class_device_names(class_name)? {
+            let address = match read_device_attribute(class_name, &name, ADDRESS_ATTRIBUTE)? {
+                Some(address) => address,
+                None => continue,
+            };
+
+            if address.contains(&port_address)
+                && driver_matches(class_name, &name, driver_name_vec)?
+            {
+                return Ok(name);
             }
         }
 
@@ -65,10 +226,75 @@ impl Driver {
         })
     }
 
+    /// Returns the name of the device with the given `class_name`, `driver_name` and at the given `channel`
+    /// of the `port`, e.g. a sensor at the address `ev3-ports:in1:i2c80` of a multiplexer.
+    ///
+    /// The address has to end with `{port}:{channel}` at a `:` boundary,
+    /// so `in1:i2c8` does not match a device at `in1:i2c80`.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such device exists.
+    pub fn find_name_by_port_and_channel(
+        class_name: &str,
+        port: &dyn Port,
+        channel: &str,
+        driver_name_vec: &[&str],
+    ) -> Ev3Result<String> {
+        let channel_address = format!("{}:{}", port.address(), channel);
+
+        for name in class_device_names(class_name)? {
+            let address = match read_device_attribute(class_name, &name, ADDRESS_ATTRIBUTE)? {
+                Some(address) => address,
+                None => continue,
+            };
+
+            if address_matches(&address, &channel_address)
+                && driver_matches(class_name, &name, driver_name_vec)?
+            {
+                return Ok(name);
+            }
+        }
+
+        Err(Ev3Error::NotConnected {
+            device: format!("{:?}", driver_name_vec),
+            port: Some(channel_address),
+        })
+    }
+
+    /// Returns the name of the device with the given `class_name`, `driver_name` and at the given `port`.
+    /// Rescans the class directory until the device appears or the `timeout` is reached.
+    /// This is useful on boot, when not all devices are enumerated yet.
+    ///
+    /// Polling `/sys/class/{class_name}` with `epoll` is not reliable across ev3dev kernels,
+    /// so the directory is rescanned every 50 milliseconds.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such device exists after the `timeout`.
+    pub fn find_name_by_port_and_driver_with_timeout(
+        class_name: &str,
+        port: &dyn Port,
+        driver_name_vec: &[&str],
+        timeout: Duration,
+    ) -> Ev3Result<String> {
+        let start = Instant::now();
+
+        loop {
+            match Driver::find_name_by_port_and_driver(class_name, port, driver_name_vec) {
+                Ok(name) => return Ok(name),
+                Err(e) => {
+                    let elapsed = start.elapsed();
+                    if elapsed >= timeout {
+                        return Err(e);
+                    }
+                    thread::sleep(RESCAN_INTERVAL.min(timeout - elapsed));
+                }
+            }
+        }
+    }
+
     /// Returns the name of the device with the given `class_name`.
     ///
-    /// Returns `Ev3Error::NotFound` if no such device exists.
+    /// Returns `Ev3Error::NotConnected` if no such device exists.
     /// Returns `Ev3Error::MultipleMatches` if more then one matching device exists.
+    /// The error contains the sysfs names and port addresses of all matching devices.
     pub fn find_name_by_driver(class_name: &str, driver_name_vec: &[&str]) -> Ev3Result<String> {
         let mut names = Driver::find_names_by_driver(class_name, driver_name_vec)?;
 
@@ -82,32 +308,88 @@ impl Driver {
                 .expect("Name vector should contains exactly one element")),
             _ => Err(Ev3Error::MultipleMatches {
                 device: format!("{:?}", driver_name_vec),
-                ports: names,
+                ports: names
+                    .iter()
+                    .map(|name| {
+                        Attribute::from_sys_class(class_name, name, ADDRESS_ATTRIBUTE)
+                            .and_then(|address| address.get::<String>())
+                            .unwrap_or_else(|_| name.to_owned())
+                    })
+                    .collect(),
+                names,
             }),
         }
     }
 
-    /// Returns the names of the devices with the given `class_name`.
+    /// Returns the name of the device with the given `class_name`.
+    /// Rescans the class directory until exactly one matching device exists or the `timeout` is reached,
+    /// like `find_name_by_port_and_driver_with_timeout`.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such device exists after the `timeout`.
+    /// Returns `Ev3Error::MultipleMatches` if more then one matching device still exists after the `timeout`.
+    pub fn find_name_by_driver_with_timeout(
+        class_name: &str,
+        driver_name_vec: &[&str],
+        timeout: Duration,
+    ) -> Ev3Result<String> {
+        let start = Instant::now();
+
+        loop {
+            match Driver::find_name_by_driver(class_name, driver_name_vec) {
+                Ok(name) => return Ok(name),
+                Err(e) => {
+                    let elapsed = start.elapsed();
+                    if elapsed >= timeout {
+                        return Err(e);
+                    }
+                    thread::sleep(RESCAN_INTERVAL.min(timeout - elapsed));
+                }
+            }
+        }
+    }
+
+    /// Returns the names of the devices with the given `class_name`, ordered by their port address.
     pub fn find_names_by_driver(
         class_name: &str,
         driver_name_vec: &[&str],
     ) -> Ev3Result<Vec<String>> {
-        let paths = fs::read_dir(format!("{}{}", ROOT_PATH, class_name))?;
+        let mut found = Vec::new();
+        for name in class_device_names(class_name)? {
+            if !driver_matches(class_name, &name, driver_name_vec)? {
+                continue;
+            }
+            if let Some(address) = read_device_attribute(class_name, &name, ADDRESS_ATTRIBUTE)? {
+                found.push((address, name));
+            }
+        }
 
-        let mut found_names = Vec::new();
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?;
+        found.sort_by_cached_key(|(address, name)| (address_order(address), name.clone()));
+        Ok(found.into_iter().map(|(_, name)| name).collect())
+    }
 
-            let driver = Attribute::from_sys_class(class_name, name, "driver_name")?;
+    /// Re-resolves the sysfs name of the device at the `port`, e.g. after the device was unplugged and plugged in again.
+    ///
+    /// Looks up the device with `find_name_by_port_and_driver` and drops all cached attributes,
+    /// so the next `get_attribute` opens the files of the new name. Clones of this driver are not affected.
+    /// Returns `Ev3Error::NotConnected` and keeps the current name if no device is connected to the `port`
+    /// or the connected device has none of the given drivers, e.g. a touch sensor was plugged in instead of a gyro sensor.
+    pub fn reconnect(&mut self, port: &dyn Port, driver_name_vec: &[&str]) -> Ev3Result<()> {
+        let name = Driver::find_name_by_port_and_driver(&self.class_name, port, driver_name_vec)?;
+        self.set_name(name);
+        Ok(())
+    }
 
-            let driver_name = driver.get::<String>()?;
-            if driver_name_vec.iter().any(|n| &driver_name == n) {
-                found_names.push(name.to_owned());
-            }
-        }
+    /// Re-resolves the sysfs name of the device at the `port` like `reconnect`, but regardless of its driver.
+    pub fn reconnect_any_driver(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        let name = Driver::find_name_by_port(&self.class_name, port)?;
+        self.set_name(name);
+        Ok(())
+    }
 
-        Ok(found_names)
+    /// Replaces the sysfs name and drops all cached attributes of the old name.
+    fn set_name(&mut self, name: String) {
+        self.name = name;
+        self.attributes.get_mut().clear();
     }
 
     /// Return the `Attribute` wrapper for the given `attribute_name`.
@@ -116,11 +398,7 @@ impl Driver {
         let mut attributes = self.attributes.borrow_mut();
 
         if !attributes.contains_key(attribute_name) {
-            if let Ok(v) = Attribute::from_sys_class(
-                self.class_name.as_ref(),
-                self.name.as_ref(),
-                attribute_name,
-            ) {
+            if let Ok(v) = self.open_attribute(attribute_name) {
                 attributes.insert(attribute_name.to_owned(), v);
             };
         };
@@ -130,6 +408,172 @@ impl Driver {
             .expect("Internal error in the attribute map")
             .clone()
     }
+
+    /// Return the `Attribute` wrapper for the given `attribute_name`.
+    /// Creates a new one if it does not exist.
+    ///
+    /// In contrast to `get_attribute` this returns `Ev3Error::InternalError`
+    /// if the device does not provide the attribute. Other errors, e.g. missing permissions,
+    /// are returned as `Ev3Error::Os`.
+    pub fn try_get_attribute(&self, attribute_name: &str) -> Ev3Result<Attribute> {
+        let mut attributes = self.attributes.borrow_mut();
+
+        if let Some(attribute) = attributes.get(attribute_name) {
+            return Ok(attribute.clone());
+        }
+
+        let attribute = self.open_attribute(attribute_name).map_err(|e| {
+            if e.raw_os_error() == Some(libc::ENOENT) {
+                Ev3Error::InternalError {
+                    msg: format!(
+                        "Attribute `{}` of device `{}` could not be found!",
+                        attribute_name, self.name
+                    ),
+                }
+            } else {
+                e
+            }
+        })?;
+        attributes.insert(attribute_name.to_owned(), attribute.clone());
+        Ok(attribute)
+    }
+
+    /// Returns the raw string values of all given `attribute_names`.
+    /// Cached `Attribute` wrappers are reused, missing ones are created and cached.
+    ///
+    /// Returns `Ev3Error::InternalError` if one of the attributes does not exist.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Driver;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let driver = Driver::new("lego-sensor", "sensor0");
+    ///
+    /// let values = driver.get_attributes(&["value0", "value1", "value2"])?;
+    /// let red: i32 = values["value0"].parse()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_attributes(&self, attribute_names: &[&str]) -> Ev3Result<HashMap<String, String>> {
+        let mut values = HashMap::with_capacity(attribute_names.len());
+        for attribute_name in attribute_names {
+            let value = self.try_get_attribute(attribute_name)?.get::<String>()?;
+            values.insert((*attribute_name).to_owned(), value);
+        }
+
+        Ok(values)
+    }
+
+    /// Writes the raw string values of several attributes in the given order, e.g. every tick of a control loop.
+    ///
+    /// All `Attribute` wrappers are looked up (and cached, like `get_attributes`) before the first write,
+    /// so the writes follow each other with one `write` call each and no file lookups in between.
+    /// The writes are not atomic: the driver sees each value as soon as it is written.
+    ///
+    /// Returns `Ev3Error::InternalError` without writing anything if one of the attributes does not exist.
+    /// Stops at the first failing write and returns its error, the previous writes stay in effect.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Driver;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let driver = Driver::new("tacho-motor", "motor0");
+    ///
+    /// driver.set_attributes(&[("duty_cycle_sp", "40"), ("command", "run-direct")])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_attributes(&self, pairs: &[(&str, &str)]) -> Ev3Result<()> {
+        let attributes = pairs
+            .iter()
+            .map(|&(attribute_name, value)| Ok((self.try_get_attribute(attribute_name)?, value)))
+            .collect::<Ev3Result<Vec<_>>>()?;
+
+        for (attribute, value) in attributes {
+            attribute.set_str(value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the names of all devices of the `class_name`.
+///
+/// A missing class directory means that no device of the class is connected, so it returns an empty list.
+/// Other errors, e.g. missing permissions on `/sys/class`, are returned as `Ev3Error::Os`,
+/// so they are not mistaken for an absent device.
+pub(crate) fn class_device_names(class_name: &str) -> Ev3Result<Vec<String>> {
+    let paths = match fs::read_dir(format!("{}{}", root_path(), class_name)) {
+        Ok(paths) => paths,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut names = Vec::new();
+    for path in paths {
+        let file_name = match path {
+            Ok(path) => path.file_name(),
+            Err(ref e) if is_removed(e.raw_os_error()) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        names.push(file_name.to_str().or_err()?.to_owned());
+    }
+    Ok(names)
+}
+
+/// Returns `true` for the errno of a device that was removed during a directory scan (`ENOENT` or `ENODEV`).
+fn is_removed(errno: Option<i32>) -> bool {
+    errno == Some(libc::ENOENT) || errno == Some(libc::ENODEV)
+}
+
+/// Reads the `attribute_name` of the device `name` during a directory scan.
+///
+/// Returns `None` if the device was removed since the scan (`ENOENT` or `ENODEV`),
+/// other errors like `EACCES` are returned as `Ev3Error::Os`.
+pub(crate) fn read_device_attribute(
+    class_name: &str,
+    name: &str,
+    attribute_name: &str,
+) -> Ev3Result<Option<String>> {
+    match Attribute::from_sys_class(class_name, name, attribute_name)
+        .and_then(|attribute| attribute.get::<String>())
+    {
+        Ok(value) => Ok(Some(value)),
+        Err(ref e) if is_removed(e.raw_os_error()) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks if the device `name` has one of the drivers of `driver_name_vec`.
+/// A device that was removed since the scan does not match.
+fn driver_matches(class_name: &str, name: &str, driver_name_vec: &[&str]) -> Ev3Result<bool> {
+    let driver_name = read_device_attribute(class_name, name, DRIVER_NAME_ATTRIBUTE)?;
+    Ok(driver_name.is_some_and(|driver_name| driver_name_vec.iter().any(|n| driver_name == *n)))
+}
+
+/// Checks if the sysfs `address` belongs to `port_address`.
+/// The `port_address` has to match the whole address or a suffix starting after a `:` delimiter,
+/// e.g. `in1` matches `ev3-ports:in1`, but neither `ev3-ports:in10` nor `ev3-ports:in1:i2c80`.
+fn address_matches(address: &str, port_address: &str) -> bool {
+    let address = address.trim();
+    address == port_address
+        || (address.ends_with(port_address)
+            && address[..address.len() - port_address.len()].ends_with(':'))
+}
+
+/// Returns the sort key of a sysfs `address`, that orders the ports of stacked BrickPi3 boards
+/// by layer and port (`S2` before `S10`) and all other addresses by their text.
+pub(crate) fn address_order(address: &str) -> (Option<BrickPi3Port>, String) {
+    let brickpi3_port = match address.parse() {
+        Ok(SensorPort::BrickPi3(port)) => Some(port),
+        _ => match address.parse() {
+            Ok(MotorPort::BrickPi3(port)) => Some(port),
+            _ => None,
+        },
+    };
+    (brickpi3_port, address.to_owned())
 }
 
 impl Debug for Driver {
@@ -1,6 +1,7 @@
 /// Helper to create a new `Device` instance.
 ///
-/// Generates `get()`, `find()` and `list()` methods. Therefore are 5 parameters required:
+/// Generates the `DRIVER_NAMES` constant and `get()`, `get_with_timeout()`, `get_on_channel()`, `find()`, `find_with_timeout()` and `list()` methods
+/// (and `from_driver()` with the `mock` feature). Therefore are 5 parameters required:
 /// * `class_name: &str`
 /// * `driver_name: &str`
 /// * `port: dyn ev3dev_lang_rust::Motor`
@@ -23,23 +24,39 @@
 #[macro_export]
 macro_rules! findable {
     ($class_name:expr, [$( $driver_name:expr ),*], $port: ty, $debug_name:expr, $port_prefix:expr) => {
+        /// Driver names of the devices that are represented by this type.
+        pub const DRIVER_NAMES: &'static [&'static str] = &[$($driver_name),*];
+
         fn map_error(e: Ev3Error) -> Ev3Error {
             match e {
                 e @ Ev3Error::InternalError { .. } => e,
+                e @ Ev3Error::Timeout => e,
+                e @ Ev3Error::Os { .. } => e,
                 Ev3Error::NotConnected { device: _, port } => Ev3Error::NotConnected {
                     device: $debug_name.to_owned(),
                     port,
                 },
-                Ev3Error::MultipleMatches { device: _, ports } => Ev3Error::MultipleMatches {
+                Ev3Error::MultipleMatches {
+                    device: _,
+                    ports,
+                    names,
+                } => Ev3Error::MultipleMatches {
                     device: $debug_name.to_owned(),
                     ports: ports
                         .iter()
                         .map(|item| <$port>::format_name(item))
                         .collect(),
+                    names,
                 },
             }
         }
 
+        /// Create a `Self` on top of the given `driver`, e.g. a `Driver::with_root` for a mock device.
+        #[cfg(feature = "mock")]
+        pub fn from_driver(driver: Driver) -> Self {
+            Self::new(driver)
+        }
+
         /// Try to get a `Self` on the given port. Returns `None` if port is not used or another device is connected.
         #[allow(clippy::vec_init_then_push)]
         pub fn get(port: $port) -> Ev3Result<Self> {
@@ -54,7 +71,48 @@ macro_rules! findable {
             Ok(Self::new(Driver::new($class_name, &name)))
         }
 
-        /// Try to find a `Self`. Only returns a motor if their is exactly one connected, `Error::NotFound` otherwise.
+        /// Try to get a `Self` on the given port. Waits up to `timeout` for the device to appear.
+        /// Returns `None` if port is not used or another device is connected after the `timeout`.
+        #[allow(clippy::vec_init_then_push)]
+        pub fn get_with_timeout(port: $port, timeout: std::time::Duration) -> Ev3Result<Self> {
+            let mut driver_name_vec = Vec::new();
+            $(
+                driver_name_vec.push($driver_name);
+            )*
+
+            let name = Driver::find_name_by_port_and_driver_with_timeout(
+                $class_name,
+                &port,
+                &driver_name_vec,
+                timeout,
+            )
+            .map_err(Self::map_error)?;
+
+            Ok(Self::new(Driver::new($class_name, &name)))
+        }
+
+        /// Try to get a `Self` on the given `channel` of the port, e.g. `i2c80` for a sensor
+        /// connected to a multiplexer at the address `ev3-ports:in1:i2c80`.
+        /// Returns `None` if the channel is not used or another device is connected.
+        #[allow(clippy::vec_init_then_push)]
+        pub fn get_on_channel(port: $port, channel: &str) -> Ev3Result<Self> {
+            let mut driver_name_vec = Vec::new();
+            $(
+                driver_name_vec.push($driver_name);
+            )*
+
+            let name = Driver::find_name_by_port_and_channel(
+                $class_name,
+                &port,
+                channel,
+                &driver_name_vec,
+            )
+            .map_err(Self::map_error)?;
+
+            Ok(Self::new(Driver::new($class_name, &name)))
+        }
+
+        /// Try to find a `Self`. Only returns a motor if their is exactly one connected, `Ev3Error::NotConnected` otherwise.
         #[allow(clippy::vec_init_then_push)]
         pub fn find() -> Ev3Result<Self> {
             let mut driver_name_vec = Vec::new();
@@ -68,7 +126,21 @@ macro_rules! findable {
             Ok(Self::new(Driver::new($class_name, &name)))
         }
 
-        /// Extract list of connected 'Self'
+        /// Try to find a `Self`. Waits up to `timeout` until exactly one is connected, see `find()`.
+        #[allow(clippy::vec_init_then_push)]
+        pub fn find_with_timeout(timeout: std::time::Duration) -> Ev3Result<Self> {
+            let mut driver_name_vec = Vec::new();
+            $(
+                driver_name_vec.push($driver_name);
+            )*
+
+            let name = Driver::find_name_by_driver_with_timeout($class_name, &driver_name_vec, timeout)
+                .map_err(Self::map_error)?;
+
+            Ok(Self::new(Driver::new($class_name, &name)))
+        }
+
+        /// Extract list of connected 'Self', ordered by port address.
         #[allow(clippy::vec_init_then_push)]
         pub fn list() -> Ev3Result<Vec<Self>> {
             let mut driver_name_vec = Vec::new();
@@ -1,19 +1,32 @@
-//! The leds on top of the EV3 brick.
+//! The brick status leds, e.g. on top of the EV3 brick.
 
 use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::{utils::OrErr, Attribute, Ev3Result};
+use crate::{
+    platform::{LedChannel, LedSide},
+    utils::{root_path, OrErr},
+    Attribute, Ev3Error, Ev3Result, Platform,
+};
 
 /// Color type.
+/// The red and green channel values are in the range `0..=255`
+/// and scaled to the `max_brightness` of the leds.
 pub type Color = (u8, u8);
 
-/// The leds on top of the EV3 brick.
+/// Interval between two brightness updates of `Led::fade_to`.
+const FADE_TICK: Duration = Duration::from_millis(20);
+
+/// The brick status leds, e.g. the two leds on top of the EV3 brick.
+/// The led channel names depend on the `Platform`.
 #[derive(Debug, Clone)]
 pub struct Led {
-    left_red: Attribute,
-    left_green: Attribute,
-    right_red: Attribute,
-    right_green: Attribute,
+    left_red: Option<Attribute>,
+    left_green: Option<Attribute>,
+    right_red: Option<Attribute>,
+    right_green: Option<Attribute>,
+    max_brightness: u32,
 }
 
 impl Led {
@@ -35,88 +48,125 @@ impl Led {
     /// LED color yellow.
     pub const COLOR_YELLOW: Color = (25, 255);
 
-    /// Create a new instance of the `Led` struct.
+    /// Create a new instance of the `Led` struct for the detected platform, see `Platform::detect`.
     pub fn new() -> Ev3Result<Led> {
-        let mut left_red_name = String::new();
-        let mut left_green_name = String::new();
-        let mut right_red_name = String::new();
-        let mut right_green_name = String::new();
+        Led::for_platform(Platform::detect())
+    }
+
+    /// Create a new instance of the `Led` struct with the led channel names of the `platform`.
+    ///
+    /// Channels that the platform does not have are ignored on writes and read as `0`.
+    /// Returns `Ev3Error::NotConnected` if none of the brick status leds is found.
+    pub fn for_platform(platform: Platform) -> Ev3Result<Led> {
+        let mut left_red = None;
+        let mut left_green = None;
+        let mut right_red = None;
+        let mut right_green = None;
+        let mut max_brightness = None;
 
-        let paths = fs::read_dir("/sys/class/leds")?;
+        let paths = fs::read_dir(format!("{}leds", root_path()))?;
 
         for path in paths {
             let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?.to_owned();
-
-            if name.contains(":brick-status") || name.contains(":ev3dev") {
-                if name.contains("led0:") || name.contains("left:") {
-                    if name.contains("red:") {
-                        left_red_name = name;
-                    } else if name.contains("green:") {
-                        left_green_name = name
-                    }
-                } else if name.contains("led1:") || name.contains("right:") {
-                    if name.contains("red:") {
-                        right_red_name = name
-                    } else if name.contains("green:") {
-                        right_green_name = name
-                    }
-                }
+            let name = file_name.to_str().or_err()?;
+
+            let slot = match platform.led_channel(name) {
+                Some((LedSide::Left, LedChannel::Red)) => &mut left_red,
+                Some((LedSide::Left, LedChannel::Green)) => &mut left_green,
+                Some((LedSide::Right, LedChannel::Red)) => &mut right_red,
+                Some((LedSide::Right, LedChannel::Green)) => &mut right_green,
+                None => continue,
+            };
+
+            // All channels of the brick status leds share the same maximum brightness.
+            if max_brightness.is_none() {
+                max_brightness =
+                    Some(Attribute::from_sys_class("leds", name, "max_brightness")?.get::<u32>()?);
             }
+            *slot = Some(Attribute::from_sys_class("leds", name, "brightness")?);
         }
 
-        let left_red = Attribute::from_sys_class("leds", left_red_name.as_str(), "brightness")?;
-        let left_green = Attribute::from_sys_class("leds", left_green_name.as_str(), "brightness")?;
-        let right_red = Attribute::from_sys_class("leds", right_red_name.as_str(), "brightness")?;
-        let right_green =
-            Attribute::from_sys_class("leds", right_green_name.as_str(), "brightness")?;
+        let max_brightness = max_brightness.ok_or_else(|| Ev3Error::NotConnected {
+            device: "leds".to_owned(),
+            port: None,
+        })?;
 
         Ok(Led {
             left_red,
             left_green,
             right_red,
             right_green,
+            max_brightness,
         })
     }
 
+    /// Returns the maximum brightness value of the led channels.
+    /// The channel values of a `Color` are scaled from `0..=255` to `0..=max_brightness`.
+    pub fn get_max_brightness(&self) -> u32 {
+        self.max_brightness
+    }
+
+    /// Reads the brightness of a channel and scales it to the range `0..=255`.
+    /// A missing channel is always off.
+    fn get_channel(&self, channel: &Option<Attribute>) -> Ev3Result<u8> {
+        let channel = match *channel {
+            Some(ref channel) => channel,
+            None => return Ok(0),
+        };
+        let brightness = channel.get::<u32>()?.min(self.max_brightness);
+        if self.max_brightness == 0 {
+            return Ok(0);
+        }
+        Ok((brightness * 255 / self.max_brightness) as u8)
+    }
+
+    /// Scales the value from the range `0..=255` to the maximum brightness and writes it to the channel.
+    /// Writes to a missing channel are ignored.
+    fn set_channel(&self, channel: &Option<Attribute>, value: u8) -> Ev3Result<()> {
+        match *channel {
+            Some(ref channel) => channel.set(u32::from(value) * self.max_brightness / 255),
+            None => Ok(()),
+        }
+    }
+
     /// Returns the current red value of the left led.
     fn get_left_red(&self) -> Ev3Result<u8> {
-        self.left_red.get()
+        self.get_channel(&self.left_red)
     }
 
     /// Sets the red value of the left led.
     fn set_left_red(&self, brightness: u8) -> Ev3Result<()> {
-        self.left_red.set(brightness)
+        self.set_channel(&self.left_red, brightness)
     }
 
     /// Returns the current green value of the left led.
     fn get_left_green(&self) -> Ev3Result<u8> {
-        self.left_green.get()
+        self.get_channel(&self.left_green)
     }
 
     /// Sets the green value of the left led.
     fn set_left_green(&self, brightness: u8) -> Ev3Result<()> {
-        self.left_green.set(brightness)
+        self.set_channel(&self.left_green, brightness)
     }
 
     /// Returns the current red value of the right led.
     fn get_right_red(&self) -> Ev3Result<u8> {
-        self.right_red.get()
+        self.get_channel(&self.right_red)
     }
 
     /// Sets the red value of the right led.
     fn set_right_red(&self, brightness: u8) -> Ev3Result<()> {
-        self.right_red.set(brightness)
+        self.set_channel(&self.right_red, brightness)
     }
 
     /// Returns the current green value of the right led.
     fn get_right_green(&self) -> Ev3Result<u8> {
-        self.right_green.get()
+        self.get_channel(&self.right_green)
     }
 
     /// Sets the green value of the right led.
     fn set_right_green(&self, brightness: u8) -> Ev3Result<()> {
-        self.right_green.set(brightness)
+        self.set_channel(&self.right_green, brightness)
     }
 
     /// Returns the current color value of the left led.
@@ -164,4 +214,49 @@ impl Led {
         self.set_left_color(color)?;
         self.set_right_color(color)
     }
+
+    /// Fades both leds from their current colors to `color` within `duration`.
+    /// The brightness is updated every 20 milliseconds. This function blocks until the fade is done.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Led;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let led = Led::new()?;
+    ///
+    /// led.set_color(Led::COLOR_OFF)?;
+    /// led.fade_to(Led::COLOR_GREEN, Duration::from_secs(1))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fade_to(&self, color: Color, duration: Duration) -> Ev3Result<()> {
+        let left = self.get_left_color()?;
+        let right = self.get_right_color()?;
+
+        let start = Instant::now();
+        loop {
+            let elapsed = start.elapsed();
+            if elapsed >= duration {
+                break;
+            }
+            let progress = elapsed.as_secs_f32() / duration.as_secs_f32();
+
+            self.set_left_color(interpolate_color(left, color, progress))?;
+            self.set_right_color(interpolate_color(right, color, progress))?;
+
+            thread::sleep(FADE_TICK.min(duration - elapsed));
+        }
+
+        self.set_color(color)
+    }
+}
+
+/// Linear interpolation between the colors `from` and `to`, `progress` is in the range `0.0..=1.0`.
+fn interpolate_color(from: Color, to: Color, progress: f32) -> Color {
+    let channel = |from: u8, to: u8| {
+        (f32::from(from) + (f32::from(to) - f32::from(from)) * progress).round() as u8
+    };
+    (channel(from.0, to.0), channel(from.1, to.1))
 }
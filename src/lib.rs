@@ -32,6 +32,49 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ## Threads
+//!
+//! Motors and sensors are `Send` but not `Sync`, because the `Driver` caches its attributes in a `RefCell`.
+//! A device can't be shared by reference between threads; instead give every thread its own clone.
+//! All clones share the underlying attribute files, each access is guarded by a mutex.
+//! `Ev3Button` and `RemoteControl` share their state through an `Rc` and are neither `Send` nor `Sync`.
+//!
+//! ```no_run
+//! use ev3dev_lang_rust::motors::LargeMotor;
+//! use std::thread;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let motor = LargeMotor::find()?;
+//!
+//! let telemetry_motor = motor.clone();
+//! let telemetry = thread::spawn(move || {
+//!     for _ in 0..10 {
+//!         println!("Speed: {}", telemetry_motor.get_speed().unwrap_or(0));
+//!     }
+//! });
+//!
+//! motor.run_forever()?;
+//! telemetry.join().unwrap();
+//! motor.stop()?;
+//! # Ok(())
+//! # }
+//! # fn assert_send<T: Send>() {}
+//! # assert_send::<ev3dev_lang_rust::motors::TachoMotor>();
+//! # assert_send::<ev3dev_lang_rust::motors::MotorGroup>();
+//! # assert_send::<ev3dev_lang_rust::sensors::ColorSensor>();
+//! # assert_send::<ev3dev_lang_rust::sensors::UltrasonicSensor>();
+//! # assert_send::<ev3dev_lang_rust::Led>();
+//! ```
+//!
+//! Devices can't be shared through an `Arc` without a `Mutex`:
+//!
+//! ```compile_fail
+//! use ev3dev_lang_rust::motors::LargeMotor;
+//!
+//! fn assert_sync<T: Sync>() {}
+//! assert_sync::<LargeMotor>();
+//! ```
 
 #[cfg(feature = "screen")]
 extern crate framebuffer;
@@ -39,6 +82,15 @@ extern crate framebuffer;
 #[cfg(feature = "screen")]
 extern crate image;
 
+#[cfg(feature = "tokio")]
+extern crate tokio;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "log")]
+extern crate log;
+
 #[macro_use]
 extern crate ev3dev_lang_rust_derive;
 extern crate libc;
@@ -52,19 +104,28 @@ mod driver;
 pub use driver::Driver;
 mod device;
 pub use device::Device;
+mod device_watcher;
+pub use device_watcher::{DeviceEvent, DeviceWatcher};
+pub mod devices;
 
 mod utils;
-pub use utils::{Ev3Error, Ev3Result, Port};
+pub use utils::{BrickPi3Port, Ev3Error, Ev3Result, Port, BRICKPI3_MAX_LAYERS};
 
 #[cfg(target_os = "linux")]
 pub mod wait;
 
+#[cfg(feature = "mock")]
+pub mod mock;
+
 pub mod motors;
 pub mod sensors;
 
 mod led;
 pub use led::Led;
 
+pub mod platform;
+pub use platform::Platform;
+
 pub mod sound;
 
 mod buttons;
@@ -77,3 +138,8 @@ pub use power_supply::PowerSupply;
 mod screen;
 #[cfg(feature = "screen")]
 pub use screen::Screen;
+
+#[cfg(all(feature = "screen", target_os = "linux"))]
+mod menu;
+#[cfg(all(feature = "screen", target_os = "linux"))]
+pub use menu::Menu;
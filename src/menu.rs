@@ -0,0 +1,146 @@
+//! Selection menu on the screen of the brick.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::screen::LINE_HEIGHT;
+use crate::wait::{self, Epoll};
+use crate::{Ev3Button, Ev3Result, Screen};
+
+/// Sleep interval between two button checks if no `epoll` instance is available.
+const FALLBACK_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Horizontal offset of the item texts in pixels.
+const TEXT_OFFSET: u32 = 2;
+
+/// List of items on the screen, that is navigated and selected with the brick buttons.
+///
+/// `up` and `down` move the selection, which wraps around at the ends of the list.
+/// `enter` selects the highlighted item and `backspace` cancels the menu.
+/// Lists that are longer than the screen are scrolled.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Menu;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let mut menu = Menu::new(&["Follow line", "Calibrate", "Exit"]);
+///
+/// match menu.run()? {
+///     Some(0) => println!("Following the line"),
+///     Some(1) => println!("Calibrating"),
+///     _ => println!("Bye"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Menu {
+    items: Vec<String>,
+    selected: usize,
+}
+
+impl Menu {
+    /// Create a new menu of the `items`. The first item is selected.
+    pub fn new(items: &[&str]) -> Self {
+        Menu {
+            items: items.iter().map(|item| (*item).to_owned()).collect(),
+            selected: 0,
+        }
+    }
+
+    /// Returns the index of the highlighted item.
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Sets the highlighted item, e.g. to start with the previous choice.
+    /// Indices past the end select the last item.
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+    }
+
+    /// Opens the screen and the buttons and runs the menu, see `run_with`.
+    pub fn run(&mut self) -> Ev3Result<Option<usize>> {
+        let mut screen = Screen::new()?;
+        let buttons = Ev3Button::new()?;
+        self.run_with(&mut screen, &buttons)
+    }
+
+    /// Draws the menu to the `screen` and blocks until an item is selected with the `buttons`.
+    ///
+    /// The screen is redrawn after every navigation.
+    /// Returns the index of the selected item or `None` if the menu is cancelled with `backspace`.
+    /// Menus without items return `None` immediately.
+    ///
+    /// Buttons that are already held down when the menu starts are ignored until they are released.
+    pub fn run_with(
+        &mut self,
+        screen: &mut Screen,
+        buttons: &Ev3Button,
+    ) -> Ev3Result<Option<usize>> {
+        if self.items.is_empty() {
+            return Ok(None);
+        }
+
+        let epoll = Epoll::create().and_then(|epoll| {
+            for fd in buttons.get_fds() {
+                epoll.add_input(fd)?;
+            }
+            Ok(epoll)
+        });
+
+        buttons.process();
+        self.draw(screen);
+
+        loop {
+            match epoll {
+                Ok(ref epoll) => {
+                    epoll.wait(wait::get_max_poll_interval());
+                }
+                Err(_) => thread::sleep(FALLBACK_INTERVAL),
+            }
+
+            buttons.process();
+            let pressed = buttons.get_just_pressed_buttons();
+
+            if pressed.contains("backspace") {
+                return Ok(None);
+            }
+            if pressed.contains("enter") {
+                return Ok(Some(self.selected));
+            }
+
+            let count = self.items.len();
+            let selected = if pressed.contains("up") {
+                (self.selected + count - 1) % count
+            } else if pressed.contains("down") {
+                (self.selected + 1) % count
+            } else {
+                continue;
+            };
+
+            if selected != self.selected {
+                self.selected = selected;
+                self.draw(screen);
+            }
+        }
+    }
+
+    /// Draws the visible part of the list with the highlighted item inverted.
+    fn draw(&self, screen: &mut Screen) {
+        let rows = (screen.yres() / LINE_HEIGHT).max(1) as usize;
+        let first = (self.selected + 1).saturating_sub(rows);
+
+        screen.clear();
+        for (row, item) in self.items.iter().enumerate().skip(first).take(rows) {
+            let y = (row - first) as u32 * LINE_HEIGHT;
+            let highlighted = row == self.selected;
+            if highlighted {
+                screen.fill_rect(0, y, screen.xres(), LINE_HEIGHT, true);
+            }
+            screen.draw_text(TEXT_OFFSET, y, item, !highlighted);
+        }
+        screen.update();
+    }
+}
@@ -0,0 +1,268 @@
+//! Fake sysfs devices for testing robot code without an ev3 brick.
+//!
+//! A `MockDevice` is a directory of regular attribute files below a custom root directory.
+//! Devices created with `Driver::with_root` read and write these files instead of `/sys/class/`.
+//!
+//! ```
+//! use ev3dev_lang_rust::mock::MockDevice;
+//! use ev3dev_lang_rust::motors::LargeMotor;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let root = std::env::temp_dir().join("ev3dev-mock-example");
+//!
+//! let device = MockDevice::new(&root, "tacho-motor", "motor0")?;
+//! device.set_attribute("speed_sp", "0")?;
+//! device.set_attribute("command", "")?;
+//! device.set_attribute("commands", "run-forever stop")?;
+//!
+//! let motor = LargeMotor::from_driver(device.driver());
+//! motor.set_speed_sp(500)?;
+//! motor.run_forever()?;
+//!
+//! assert_eq!(device.get_attribute("speed_sp")?, "500");
+//! assert_eq!(device.get_attribute("command")?, "run-forever");
+//! # std::fs::remove_dir_all(&root)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Dry-run mode
+//!
+//! Without any attribute files, the dry-run mode keeps all attributes in memory. While it is enabled with
+//! `set_dry_run`, every newly created `Attribute` reads the value seeded by `seed_value`
+//! (or an empty string) and accepts every write. The writes are recorded in order and can be
+//! inspected with `recorded_writes`. Later reads return the last written value, like a mock device.
+//! On Linux the values are held in anonymous memory files (`memfd_create`), other platforms use
+//! unlinked files in `std::env::temp_dir()`.
+//!
+//! The mode and the recorded writes are global, so tests using the dry-run mode should not run in parallel
+//! with tests that access real or mock devices.
+//!
+//! ```
+//! use ev3dev_lang_rust::mock::{self, RecordedWrite};
+//! use ev3dev_lang_rust::motors::LargeMotor;
+//! use ev3dev_lang_rust::Driver;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! mock::set_dry_run(true);
+//! mock::seed_value("/sys/class/tacho-motor/motor0/count_per_rot", "360")?;
+//! mock::seed_value("/sys/class/tacho-motor/motor0/commands", "run-forever stop")?;
+//!
+//! let motor = LargeMotor::from_driver(Driver::new("tacho-motor", "motor0"));
+//! motor.set_speed_sp(500)?;
+//! motor.run_forever()?;
+//!
+//! assert_eq!(motor.get_count_per_rot()?, 360);
+//! assert_eq!(
+//!     mock::recorded_writes(),
+//!     vec![
+//!         RecordedWrite::new("/sys/class/tacho-motor/motor0/speed_sp", "500"),
+//!         RecordedWrite::new("/sys/class/tacho-motor/motor0/command", "run-forever"),
+//!     ]
+//! );
+//!
+//! mock::reset_dry_run();
+//! mock::set_dry_run(false);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+#[cfg(not(target_os = "linux"))]
+use std::fs::OpenOptions;
+use std::fs::{self, File, Permissions};
+#[cfg(target_os = "linux")]
+use std::io;
+use std::os::unix::fs::{FileExt, PermissionsExt};
+#[cfg(target_os = "linux")]
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::path::{Path, PathBuf};
+#[cfg(not(target_os = "linux"))]
+use std::process;
+#[cfg(not(target_os = "linux"))]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::{Driver, Ev3Result};
+
+/// Enables the dry-run mode, see `set_dry_run`.
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// In-memory attribute files of the dry-run mode by their path, shared by all attributes of the same path.
+static DRY_RUN_FILES: Mutex<BTreeMap<String, Arc<Mutex<File>>>> = Mutex::new(BTreeMap::new());
+
+/// Writes of the dry-run mode in order, see `recorded_writes`.
+static RECORDED_WRITES: Mutex<Vec<RecordedWrite>> = Mutex::new(Vec::new());
+
+/// Number of created temporary files, used for unique file names.
+#[cfg(not(target_os = "linux"))]
+static DRY_RUN_FILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// A fake device directory `{root}/{class_name}/{name}` with regular attribute files.
+#[derive(Debug, Clone)]
+pub struct MockDevice {
+    root: PathBuf,
+    class_name: String,
+    name: String,
+}
+
+impl MockDevice {
+    /// Creates the device directory `{root}/{class_name}/{name}` if it does not exist.
+    pub fn new(root: &Path, class_name: &str, name: &str) -> Ev3Result<MockDevice> {
+        fs::create_dir_all(root.join(class_name).join(name))?;
+
+        Ok(MockDevice {
+            root: root.to_owned(),
+            class_name: class_name.to_owned(),
+            name: name.to_owned(),
+        })
+    }
+
+    /// Returns the path of the attribute file `attribute_name`.
+    fn path(&self, attribute_name: &str) -> PathBuf {
+        self.root
+            .join(&self.class_name)
+            .join(&self.name)
+            .join(attribute_name)
+    }
+
+    /// Creates or overwrites the attribute file `attribute_name` with the given `value`.
+    /// The file is made readable and writable for the group, like the ev3dev sysfs attributes.
+    pub fn set_attribute(&self, attribute_name: &str, value: &str) -> Ev3Result<()> {
+        let path = self.path(attribute_name);
+        fs::write(&path, value)?;
+        fs::set_permissions(&path, Permissions::from_mode(0o660))?;
+        Ok(())
+    }
+
+    /// Returns the current value of the attribute file `attribute_name` without trailing whitespace.
+    pub fn get_attribute(&self, attribute_name: &str) -> Ev3Result<String> {
+        let value = fs::read_to_string(self.path(attribute_name))?;
+        Ok(value.trim_end().to_owned())
+    }
+
+    /// Returns a new `Driver` for this device.
+    pub fn driver(&self) -> Driver {
+        Driver::with_root(&self.root, &self.class_name, &self.name)
+    }
+}
+
+/// A write to an attribute in dry-run mode, see `recorded_writes`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedWrite {
+    /// Path of the attribute, e.g. `/sys/class/tacho-motor/motor0/command`.
+    pub path: String,
+    /// Written value. Binary writes are converted lossy to UTF-8.
+    pub value: String,
+}
+
+impl RecordedWrite {
+    /// Create a new `RecordedWrite`, e.g. to compare it with the `recorded_writes`.
+    pub fn new(path: &str, value: &str) -> RecordedWrite {
+        RecordedWrite {
+            path: path.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+}
+
+/// Enables or disables the dry-run mode. Only attributes created while the mode is enabled are kept in memory,
+/// existing attributes keep their files.
+pub fn set_dry_run(enabled: bool) {
+    DRY_RUN.store(enabled, Ordering::SeqCst);
+}
+
+/// Returns `true` if the dry-run mode is enabled.
+pub fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::SeqCst)
+}
+
+/// Sets the value of the in-memory attribute at `path`, e.g. `/sys/class/tacho-motor/motor0/count_per_rot`.
+/// Attributes of this path, that already exist or are created later in dry-run mode, read the new value.
+/// Seeding is not recorded as write.
+pub fn seed_value(path: &str, value: &str) -> Ev3Result<()> {
+    let file = dry_run_file(path)?;
+    let file = file.lock().unwrap();
+    file.set_len(0)?;
+    file.write_all_at(value.as_bytes(), 0)?;
+    Ok(())
+}
+
+/// Returns all writes since the dry-run mode was enabled or reset, in order.
+pub fn recorded_writes() -> Vec<RecordedWrite> {
+    RECORDED_WRITES.lock().unwrap().clone()
+}
+
+/// Drops the recorded writes and all seeded and written values.
+/// Attributes that are still alive keep their current values.
+pub fn reset_dry_run() {
+    RECORDED_WRITES.lock().unwrap().clear();
+    DRY_RUN_FILES.lock().unwrap().clear();
+}
+
+/// Returns the in-memory file of the attribute at `path` in dry-run mode, `None` if the mode is disabled.
+pub(crate) fn open_dry_run_file(path: &str) -> Ev3Result<Option<Arc<Mutex<File>>>> {
+    if is_dry_run() {
+        dry_run_file(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Records a write to the attribute at `path` in dry-run mode.
+pub(crate) fn record_write(path: &str, bytes: &[u8]) {
+    RECORDED_WRITES
+        .lock()
+        .unwrap()
+        .push(RecordedWrite::new(path, &String::from_utf8_lossy(bytes)));
+}
+
+/// Returns the in-memory file of the attribute at `path` and creates an empty one if it does not exist yet.
+///
+/// The file supports every operation of an attribute file and is released with the last attribute that uses it.
+fn dry_run_file(path: &str) -> Ev3Result<Arc<Mutex<File>>> {
+    let mut files = DRY_RUN_FILES.lock().unwrap();
+    if let Some(file) = files.get(path) {
+        return Ok(file.clone());
+    }
+
+    let file = Arc::new(Mutex::new(anonymous_file()?));
+    files.insert(path.to_owned(), file.clone());
+    Ok(file)
+}
+
+/// Creates an anonymous file in memory.
+///
+/// Uses the raw syscall, because the `memfd_create` wrapper is missing in the glibc of ev3dev stretch.
+#[cfg(target_os = "linux")]
+fn anonymous_file() -> Ev3Result<File> {
+    let fd = unsafe {
+        libc::syscall(
+            libc::SYS_memfd_create,
+            b"ev3dev-dry-run\0".as_ptr() as *const libc::c_char,
+            libc::MFD_CLOEXEC,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(unsafe { File::from_raw_fd(fd as RawFd) })
+}
+
+/// Creates an unlinked temporary file, that is removed when it is closed.
+#[cfg(not(target_os = "linux"))]
+fn anonymous_file() -> Ev3Result<File> {
+    let temp_path = std::env::temp_dir().join(format!(
+        "ev3dev-dry-run-{}-{}",
+        process::id(),
+        DRY_RUN_FILE_COUNT.fetch_add(1, Ordering::SeqCst)
+    ));
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&temp_path)?;
+    fs::remove_file(&temp_path)?;
+    Ok(file)
+}
@@ -0,0 +1,37 @@
+use super::MotorPort;
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
+use std::time::Duration;
+
+/// Regular DC motor without feedback, like the LEGO MINDSTORMS RCX motors and the LEGO Power Functions motors.
+///
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{DcMotor, MotorPort};
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = DcMotor::get(MotorPort::OutA)?;
+///
+/// motor.set_duty_cycle_sp(75)?;
+/// motor.run_timed(Some(Duration::from_secs(2)))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device)]
+pub struct DcMotor {
+    driver: Driver,
+}
+
+impl DcMotor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!(
+        "dc-motor",
+        ["rcx-motor", "dc-motor"],
+        MotorPort,
+        "DcMotor",
+        "out"
+    );
+    dc_motor!();
+}
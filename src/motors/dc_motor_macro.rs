@@ -61,13 +61,22 @@ macro_rules! dc_motor {
         }
 
         /// Returns the current polarity of the motor.
-        pub fn get_polarity(&self) -> Ev3Result<String> {
+        pub fn get_polarity(&self) -> Ev3Result<$crate::motors::Polarity> {
             self.get_attribute("polarity").get()
         }
 
         /// Sets the polarity of the motor.
-        pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
-            self.get_attribute("polarity").set_str_slice(polarity)
+        pub fn set_polarity(&self, polarity: $crate::motors::Polarity) -> Ev3Result<()> {
+            self.get_attribute("polarity")
+                .set_str_slice(polarity.as_str())
+        }
+
+        /// Inverts the current polarity of the motor, e.g. for a wheel that is mounted backwards.
+        /// Returns the new polarity.
+        pub fn toggle_polarity(&self) -> Ev3Result<$crate::motors::Polarity> {
+            let polarity = self.get_polarity()?.inverted();
+            self.set_polarity(polarity)?;
+            Ok(polarity)
         }
 
         /// Returns the current ramp up setpoint.
@@ -150,8 +159,7 @@ macro_rules! dc_motor {
         /// and then stops the motor using the command specified by `stop_action`.
         pub fn run_timed(&self, time_sp: Option<Duration>) -> Ev3Result<()> {
             if let Some(duration) = time_sp {
-                let p = duration.as_millis() as i32;
-                self.set_time_sp(p)?;
+                self.set_time_sp(duration.as_millis().min(i32::MAX as u128) as i32)?;
             }
             self.set_command(Self::COMMAND_RUN_TIMED)
         }
@@ -0,0 +1,376 @@
+//! Differential drive of a robot with two driven wheels.
+
+use std::f32::consts::PI;
+#[cfg(target_os = "linux")]
+use std::thread;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
+
+use super::{MotorGroup, TachoMotor};
+use crate::{Ev3Error, Ev3Result};
+
+/// Interval between two corrections of `DriveBase::straight_synced`.
+#[cfg(target_os = "linux")]
+const SYNC_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Duty cycle correction of `DriveBase::straight_synced` in percent per tacho count of difference.
+#[cfg(target_os = "linux")]
+const SYNC_GAIN: f32 = 0.5;
+
+/// Time without progress after which `DriveBase::straight_synced` gives up.
+#[cfg(target_os = "linux")]
+const SYNC_STALL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Position and heading of a `DriveBase`, estimated by `DriveBase::update_pose`.
+///
+/// The pose is relative to the pose at the first update after creation or `reset_pose`:
+/// `x` points forward, `y` to the right and `heading` is measured clockwise, like the angles of `DriveBase::turn`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Pose {
+    /// Forward distance in millimeters.
+    pub x: f32,
+    /// Distance to the right in millimeters.
+    pub y: f32,
+    /// Heading in degrees, clockwise.
+    pub heading: f32,
+}
+
+/// Differential drive of a robot with two driven wheels, e.g. the typical EV3 driving base.
+///
+/// The geometry is given in millimeters: the `wheel_diameter` of both wheels and the `axle_track`,
+/// the distance between the contact points of the wheels with the ground.
+/// Positive turn rates and angles turn the robot clockwise (to the right).
+///
+/// The motors are started together with a `MotorGroup`. Distances and speeds are converted
+/// with the cached `count_per_rot` attribute of the left motor.
+///
+/// The pose of the robot can be tracked by calling `update_pose` regularly,
+/// see `Pose` for the coordinate system.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{DriveBase, MotorPort, TachoMotor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let left = TachoMotor::get(MotorPort::OutB)?;
+/// let right = TachoMotor::get(MotorPort::OutC)?;
+///
+/// let mut drive = DriveBase::new(left, right, 56.0, 114.0);
+/// drive.set_straight_speed(300.0);
+///
+/// // Drive a square.
+/// for _ in 0..4 {
+///     drive.straight(500.0)?;
+///     drive.turn(90.0)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DriveBase {
+    group: MotorGroup,
+    wheel_diameter: f32,
+    axle_track: f32,
+    straight_speed: f32,
+    turn_rate: f32,
+    pose: Pose,
+    /// Wheel positions of the last `update_pose`, `None` before the first update.
+    last_positions: Option<(i32, i32)>,
+}
+
+impl DriveBase {
+    /// Create a new drive base of the `left` and `right` motor with the `wheel_diameter`
+    /// and the `axle_track` in millimeters.
+    ///
+    /// `straight` defaults to 200 mm/s, `turn` to 90 degrees per second.
+    pub fn new(left: TachoMotor, right: TachoMotor, wheel_diameter: f32, axle_track: f32) -> Self {
+        DriveBase {
+            group: MotorGroup::new(vec![left, right]),
+            wheel_diameter,
+            axle_track,
+            straight_speed: 200.0,
+            turn_rate: 90.0,
+            pose: Pose::default(),
+            last_positions: None,
+        }
+    }
+
+    /// Returns the left motor.
+    pub fn left(&self) -> &TachoMotor {
+        &self.group.motors()[0]
+    }
+
+    /// Returns the right motor.
+    pub fn right(&self) -> &TachoMotor {
+        &self.group.motors()[1]
+    }
+
+    /// Sets the `wheel_diameter` and the `axle_track` in millimeters, e.g. after a calibration run.
+    /// The accumulated pose is kept.
+    pub fn set_geometry(&mut self, wheel_diameter: f32, axle_track: f32) {
+        self.wheel_diameter = wheel_diameter;
+        self.axle_track = axle_track;
+    }
+
+    /// Sets the speed of `straight` in millimeters per second.
+    pub fn set_straight_speed(&mut self, speed: f32) {
+        self.straight_speed = speed.abs();
+    }
+
+    /// Sets the turn rate of `turn` in degrees per second.
+    pub fn set_turn_rate(&mut self, turn_rate: f32) {
+        self.turn_rate = turn_rate.abs();
+    }
+
+    /// Returns the number of tacho counts per millimeter driven by a wheel.
+    fn counts_per_mm(&self) -> Ev3Result<f32> {
+        let count_per_rot = match self.left().count_per_rot() {
+            Some(count_per_rot) => count_per_rot,
+            None => self.left().get_count_per_rot()?,
+        };
+        if count_per_rot <= 0 || self.wheel_diameter <= 0.0 {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Invalid drive base geometry: count_per_rot {}, wheel diameter {}",
+                    count_per_rot, self.wheel_diameter
+                ),
+            });
+        }
+        Ok(count_per_rot as f32 / (PI * self.wheel_diameter))
+    }
+
+    /// Converts a distance of the wheels in millimeters to tacho counts.
+    fn to_counts(&self, distance: f32) -> Ev3Result<i32> {
+        Ok((distance * self.counts_per_mm()?).round() as i32)
+    }
+
+    /// Returns the distance in millimeters that each wheel travels when the robot turns by `angle` degrees.
+    fn turn_distance(&self, angle: f32) -> f32 {
+        PI * self.axle_track * angle / 360.0
+    }
+
+    /// Starts driving with the `speed` in millimeters per second and the `turn_rate` in degrees per second
+    /// until another command is sent.
+    pub fn drive(&self, speed: f32, turn_rate: f32) -> Ev3Result<()> {
+        let turn_speed = self.turn_distance(turn_rate);
+        let left = self.to_counts(speed + turn_speed)?;
+        let right = self.to_counts(speed - turn_speed)?;
+
+        self.group.set_speed_sp(&[left, right])?;
+        self.group.run_forever()
+    }
+
+    /// Drives straight for the `distance` in millimeters with the straight speed and waits until the robot stopped.
+    /// A negative `distance` drives backwards.
+    #[cfg(target_os = "linux")]
+    pub fn straight(&self, distance: f32) -> Ev3Result<()> {
+        let speed = self.to_counts(self.straight_speed)?;
+        let counts = self.to_counts(distance)?;
+
+        self.run_to_rel_pos(speed, counts, counts)
+    }
+
+    /// Drives straight for the `distance` in millimeters with the straight speed like `straight`,
+    /// but keeps both wheels at the same position while driving.
+    ///
+    /// Both motors run with `run-direct`, starting at the duty cycle of the straight speed relative to `max_speed`.
+    /// Every 10 milliseconds the positions are read and the duty cycle of the wheel ahead is reduced
+    /// and the duty cycle of the other wheel increased in proportion to their difference.
+    /// This compensates different loads of the wheels, which let independent position commands drift apart.
+    /// The motors are stopped with their `stop_action` once the average distance is reached or an error occurs.
+    ///
+    /// Returns `Ev3Error::InternalError` if the straight speed is zero or a motor is stalled,
+    /// and `Ev3Error::Timeout` if the wheels did not move for one second, e.g. because the robot is blocked.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::motors::{DriveBase, MotorPort, StopAction, TachoMotor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let left = TachoMotor::get(MotorPort::OutB)?;
+    /// let right = TachoMotor::get(MotorPort::OutC)?;
+    /// left.set_stop_action(StopAction::Hold)?;
+    /// right.set_stop_action(StopAction::Hold)?;
+    ///
+    /// let drive = DriveBase::new(left, right, 56.0, 114.0);
+    /// drive.straight_synced(1000.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn straight_synced(&self, distance: f32) -> Ev3Result<()> {
+        let speed = self.to_counts(self.straight_speed)?.saturating_abs();
+        if speed == 0 {
+            return Err(Ev3Error::InternalError {
+                msg: "The straight speed of a synced drive must not be zero".to_owned(),
+            });
+        }
+        let max_speed = self.group.motors()[0].get_max_speed()?;
+        if max_speed <= 0 {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Invalid max_speed `{}`", max_speed),
+            });
+        }
+        let duty_cycle = (speed as f32 * 100.0 / max_speed as f32).min(100.0);
+        let counts = self.to_counts(distance)?;
+
+        let result = self.run_synced(duty_cycle, counts);
+        let stop = self.stop_synced();
+        result.and(stop)
+    }
+
+    /// Control loop of `straight_synced`, runs until the wheels moved by `counts` on average.
+    #[cfg(target_os = "linux")]
+    fn run_synced(&self, duty_cycle: f32, counts: i32) -> Ev3Result<()> {
+        let direction = counts.signum();
+        let target = counts.saturating_abs();
+        let odometry = self.group.get_odometry()?;
+        let start = (odometry[0].0, odometry[1].0);
+
+        let mut started = false;
+        let mut last_progress = (i64::MIN, Instant::now());
+
+        loop {
+            let odometry = self.group.get_odometry()?;
+            let left = odometry[0]
+                .0
+                .wrapping_sub(start.0)
+                .saturating_mul(direction);
+            let right = odometry[1]
+                .0
+                .wrapping_sub(start.1)
+                .saturating_mul(direction);
+
+            let average = (i64::from(left) + i64::from(right)) / 2;
+            if average >= i64::from(target) {
+                return Ok(());
+            }
+
+            if average > last_progress.0 {
+                last_progress = (average, Instant::now());
+            } else if last_progress.1.elapsed() >= SYNC_STALL_TIMEOUT {
+                return Err(Ev3Error::Timeout);
+            }
+            if started {
+                for motor in self.group.motors() {
+                    if motor.is_stalled()? {
+                        return Err(Ev3Error::InternalError {
+                            msg: "A motor of the drive base is stalled".to_owned(),
+                        });
+                    }
+                }
+            }
+
+            let correction =
+                (SYNC_GAIN * left.saturating_sub(right) as f32).clamp(-duty_cycle, duty_cycle);
+            let duty_cycles = [duty_cycle - correction, duty_cycle + correction];
+            for (motor, duty_cycle) in self.group.motors().iter().zip(&duty_cycles) {
+                motor.set_duty_cycle_sp(direction * duty_cycle.round() as i32)?;
+            }
+            // Changes of `duty_cycle_sp` take effect immediately while running with `run-direct`.
+            if !started {
+                self.group.run_direct()?;
+                started = true;
+            }
+
+            thread::sleep(SYNC_INTERVAL);
+        }
+    }
+
+    /// Resets the duty cycles of `straight_synced` and stops the motors.
+    #[cfg(target_os = "linux")]
+    fn stop_synced(&self) -> Ev3Result<()> {
+        let stop = self.group.stop();
+        for motor in self.group.motors() {
+            motor.set_duty_cycle_sp(0)?;
+        }
+        stop
+    }
+
+    /// Turns in place by the `angle` in degrees with the turn rate and waits until the robot stopped.
+    /// Positive angles turn clockwise.
+    #[cfg(target_os = "linux")]
+    pub fn turn(&self, angle: f32) -> Ev3Result<()> {
+        let speed = self.to_counts(self.turn_distance(self.turn_rate))?;
+        let counts = self.to_counts(self.turn_distance(angle))?;
+
+        self.run_to_rel_pos(speed, counts, -counts)
+    }
+
+    /// Runs both wheels by the given tacho counts with the `speed` and waits until they stopped.
+    #[cfg(target_os = "linux")]
+    fn run_to_rel_pos(&self, speed: i32, left: i32, right: i32) -> Ev3Result<()> {
+        self.group.set_speed_sp(&[speed, speed])?;
+        self.group.run_to_rel_pos(&[left, right])?;
+        self.group.wait_until_not_moving(None);
+        Ok(())
+    }
+
+    /// Returns the pose estimated by the last `update_pose`.
+    pub fn pose(&self) -> Pose {
+        self.pose
+    }
+
+    /// Resets the pose to the origin. The next `update_pose` starts tracking from the current wheel positions.
+    pub fn reset_pose(&mut self) {
+        self.pose = Pose::default();
+        self.last_positions = None;
+    }
+
+    /// Reads both wheel positions and accumulates the movement since the last update into the pose.
+    ///
+    /// The differential drive model assumes that the wheels do not slip and that the robot moves
+    /// on a circular arc between two updates, so this should be called frequently, e.g. every 10-50 milliseconds.
+    /// The first update after creation or `reset_pose` only stores the wheel positions.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::motors::{DriveBase, MotorPort, TachoMotor};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let left = TachoMotor::get(MotorPort::OutB)?;
+    /// let right = TachoMotor::get(MotorPort::OutC)?;
+    /// let mut drive = DriveBase::new(left, right, 56.0, 114.0);
+    ///
+    /// drive.update_pose()?;
+    /// drive.drive(200.0, 30.0)?;
+    ///
+    /// for _ in 0..100 {
+    ///     thread::sleep(Duration::from_millis(20));
+    ///     let pose = drive.update_pose()?;
+    ///     println!("x: {:.0} y: {:.0} heading: {:.1}", pose.x, pose.y, pose.heading);
+    /// }
+    /// drive.stop()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_pose(&mut self) -> Ev3Result<Pose> {
+        let odometry = self.group.get_odometry()?;
+        let positions = (odometry[0].0, odometry[1].0);
+
+        if let Some((last_left, last_right)) = self.last_positions {
+            let counts_per_mm = self.counts_per_mm()?;
+            let left = positions.0.wrapping_sub(last_left) as f32 / counts_per_mm;
+            let right = positions.1.wrapping_sub(last_right) as f32 / counts_per_mm;
+
+            let distance = (left + right) / 2.0;
+            let rotation = (left - right) / self.axle_track;
+            let heading = self.pose.heading.to_radians() + rotation / 2.0;
+
+            self.pose.x += distance * heading.cos();
+            self.pose.y += distance * heading.sin();
+            self.pose.heading += rotation.to_degrees();
+        }
+        self.last_positions = Some(positions);
+
+        Ok(self.pose)
+    }
+
+    /// Stops both motors using the command specified by their `stop_action`.
+    pub fn stop(&self) -> Ev3Result<()> {
+        self.group.stop()
+    }
+}
@@ -1,18 +1,38 @@
 use super::MotorPort;
 #[cfg(target_os = "linux")]
 use crate::wait;
-use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result, Port};
+use std::cell::{Cell, RefCell};
 use std::time::Duration;
 
 /// EV3/NXT large servo motor
-#[derive(Debug, Clone, Device)]
+#[derive(Debug, Clone)]
 pub struct LargeMotor {
     driver: Driver,
+    /// Cached value of the constant `max_speed` attribute.
+    max_speed: Cell<Option<i32>>,
+    /// Cached value of the constant `count_per_rot` attribute.
+    count_per_rot: Cell<Option<i32>>,
+    /// Cached value of the constant `count_per_m` attribute.
+    count_per_m: Cell<Option<i32>>,
+    /// Cached value of the constant `commands` attribute.
+    commands: RefCell<Option<Vec<String>>>,
+    /// Cached value of the constant `stop_actions` attribute.
+    stop_actions: RefCell<Option<Vec<String>>>,
 }
 
 impl LargeMotor {
     fn new(driver: Driver) -> Self {
-        Self { driver }
+        let motor = Self {
+            driver,
+            max_speed: Cell::new(None),
+            count_per_rot: Cell::new(None),
+            count_per_m: Cell::new(None),
+            commands: RefCell::new(None),
+            stop_actions: RefCell::new(None),
+        };
+        motor.read_constants();
+        motor
     }
 
     findable!(
@@ -24,3 +44,22 @@ impl LargeMotor {
     );
     tacho_motor!();
 }
+
+impl Device for LargeMotor {
+    fn get_attribute(&self, name: &str) -> Attribute {
+        self.driver.get_attribute(name)
+    }
+
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        self.driver.try_get_attribute(name)
+    }
+
+    /// Re-resolves the motor at the `port` and reads the cached constant attributes of the new device.
+    fn reconnect(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        self.driver
+            .reconnect(port, Self::DRIVER_NAMES)
+            .map_err(Self::map_error)?;
+        self.read_constants();
+        Ok(())
+    }
+}
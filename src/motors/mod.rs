@@ -7,6 +7,12 @@ mod servo_motor_macro;
 #[macro_use]
 mod tacho_motor_macro;
 
+mod dc_motor;
+pub use self::dc_motor::DcMotor;
+
+mod servo_motor;
+pub use self::servo_motor::ServoMotor;
+
 mod large_motor;
 pub use self::large_motor::LargeMotor;
 
@@ -16,10 +22,133 @@ pub use self::medium_motor::MediumMotor;
 mod tacho_motor;
 pub use self::tacho_motor::TachoMotor;
 
-use crate::Port;
+mod motor_config;
+pub use self::motor_config::MotorConfig;
+
+mod motor_snapshot;
+pub use self::motor_snapshot::MotorSnapshot;
+
+mod motor_state;
+pub use self::motor_state::MotorState;
+
+mod pid;
+pub use self::pid::Pid;
+
+mod motor_group;
+pub use self::motor_group::MotorGroup;
+
+mod drive_base;
+pub use self::drive_base::{DriveBase, Pose};
+
+mod position_controller;
+pub use self::position_controller::PositionController;
+
+mod units;
+pub use self::units::{Counts, CountsPerSec, Degrees, IntoCounts, Rotations};
+
+mod shutdown;
+pub use self::shutdown::{run_with_graceful_shutdown, MotorStopGuard};
 
-/// EV3 ports `outA` to `outD`
-#[derive(Debug, Copy, Clone)]
+use std::fmt;
+use std::fs;
+use std::io;
+use std::panic;
+use std::str::FromStr;
+
+use crate::{
+    utils::{root_path, OrErr},
+    Attribute, BrickPi3Port, Ev3Error, Ev3Result, Port,
+};
+
+/// Sends the `stop` command to every tacho and dc motor, regardless of its driver.
+/// Each motor stops with its current `stop_action`.
+///
+/// A failing motor does not prevent the remaining motors from being stopped.
+/// Returns the first error encountered.
+///
+/// This function opens and writes sysfs files and allocates the paths, so it is not
+/// async-signal-safe and must not be called from a raw signal handler.
+/// Call it from a panic hook, a Ctrl-C handler that runs on a normal thread, or the main loop instead.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors;
+/// use std::panic;
+///
+/// panic::set_hook(Box::new(|info| {
+///     motors::stop_all().ok();
+///     eprintln!("{}", info);
+/// }));
+/// ```
+pub fn stop_all() -> Ev3Result<()> {
+    let mut result = Ok(());
+    for command in motor_commands() {
+        let stop = command.and_then(|command| command.set_str("stop"));
+        if result.is_ok() {
+            result = stop;
+        }
+    }
+    result
+}
+
+/// Opens the `command` attribute of every tacho and dc motor.
+/// Missing class directories are skipped, failures are returned in place of the attribute.
+fn motor_commands() -> Vec<Ev3Result<Attribute>> {
+    let mut commands = Vec::new();
+
+    for class_name in &["tacho-motor", "dc-motor"] {
+        let paths = match fs::read_dir(format!("{}{}", root_path(), class_name)) {
+            Ok(paths) => paths,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                commands.push(Err(e.into()));
+                continue;
+            }
+        };
+
+        for path in paths {
+            commands.push(path.map_err(Ev3Error::from).and_then(|path| {
+                let file_name = path.file_name();
+                let name = file_name.to_str().or_err()?;
+                Attribute::from_sys_class(class_name, name, "command")
+            }));
+        }
+    }
+
+    commands
+}
+
+/// Installs a panic hook that stops all motors with `stop_all` and then calls the previously installed hook,
+/// e.g. the default hook that prints the panic message.
+///
+/// The hook runs before the stack is unwound, and also with `panic = "abort"`, where it runs right before the
+/// process aborts. A panic inside the hook itself aborts immediately, so motors may keep running in that case.
+/// Errors of `stop_all` are ignored.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{self, LargeMotor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// motors::install_panic_stop_hook();
+///
+/// let motor = LargeMotor::find()?;
+/// motor.run_forever()?;
+///
+/// // The motor is stopped before the panic message is printed.
+/// panic!("Something went wrong");
+/// # }
+/// ```
+pub fn install_panic_stop_hook() {
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        stop_all().ok();
+        previous_hook(info);
+    }));
+}
+
+/// EV3 ports `outA` to `outD` and the output ports of stacked BrickPi3 boards
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum MotorPort {
     /// EV3 `outA` port
     OutA,
@@ -29,9 +158,45 @@ pub enum MotorPort {
     OutC,
     /// EV3 `outD` port
     OutD,
+    /// Output port of a stacked BrickPi3 board, the ports `1` to `4` are `A` to `D`.
+    ///
+    /// The ports of all layers are lettered consecutively, so the first port of the second layer
+    /// is `ME` (`spi0.1:ME`).
+    BrickPi3(BrickPi3Port),
 }
 
 impl MotorPort {
+    /// Returns the BrickPi3 port with the same letter as this port at the `layer` of the stack,
+    /// e.g. `MotorPort::OutA.on_layer(2)` is the port `ME`.
+    ///
+    /// Returns `Ev3Error::InternalError` if the `layer` is not `1` to `BRICKPI3_MAX_LAYERS`.
+    ///
+    /// # Example
+    /// ```
+    /// use ev3dev_lang_rust::motors::MotorPort;
+    /// use ev3dev_lang_rust::{BrickPi3Port, Port};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let port = MotorPort::OutA.on_layer(2)?;
+    /// assert_eq!(port, MotorPort::BrickPi3(BrickPi3Port::new(2, 1)?));
+    /// assert_eq!(port.address(), "ME");
+    /// assert_eq!(port.layer(), Some(2));
+    ///
+    /// assert!(MotorPort::OutD.on_layer(5).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_layer(self, layer: u8) -> Ev3Result<MotorPort> {
+        let port = match self {
+            MotorPort::OutA => 1,
+            MotorPort::OutB => 2,
+            MotorPort::OutC => 3,
+            MotorPort::OutD => 4,
+            MotorPort::BrickPi3(port) => port.port(),
+        };
+        Ok(MotorPort::BrickPi3(BrickPi3Port::new(layer, port)?))
+    }
+
     /// Try to format a device name path to a  port name.
     pub fn format_name(name: &str) -> String {
         match name {
@@ -51,6 +216,168 @@ impl Port for MotorPort {
             MotorPort::OutB => "outB".to_owned(),
             MotorPort::OutC => "outC".to_owned(),
             MotorPort::OutD => "outD".to_owned(),
+            MotorPort::BrickPi3(port) => format!("M{}", (b'A' + port.index()) as char),
+        }
+    }
+
+    fn layer(&self) -> Option<u8> {
+        match self {
+            MotorPort::BrickPi3(port) => Some(port.layer()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for MotorPort {
+    type Err = Ev3Error;
+
+    /// Parses an ev3dev port address like `ev3-ports:outC` or a port name like `outC`.
+    /// BrickPi3 addresses like `spi0.1:ME` are parsed to `MotorPort::BrickPi3` with the layer of the port.
+    ///
+    /// Returns `Ev3Error::InternalError` if the address does not belong to any port.
+    ///
+    /// # Example
+    /// ```
+    /// use ev3dev_lang_rust::motors::MotorPort;
+    /// use ev3dev_lang_rust::{BrickPi3Port, Port};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// assert_eq!("ev3-ports:outC".parse::<MotorPort>()?, MotorPort::OutC);
+    /// assert_eq!(MotorPort::OutC.address().parse::<MotorPort>()?, MotorPort::OutC);
+    /// assert_eq!(
+    ///     "spi0.1:ME".parse::<MotorPort>()?,
+    ///     MotorPort::BrickPi3(BrickPi3Port::new(2, 1)?)
+    /// );
+    /// assert!("spi0.1:MQ".parse::<MotorPort>().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        address
+            .split(':')
+            .filter_map(|part| match part {
+                "outA" => Some(MotorPort::OutA),
+                "outB" => Some(MotorPort::OutB),
+                "outC" => Some(MotorPort::OutC),
+                "outD" => Some(MotorPort::OutD),
+                _ => parse_brickpi3_port(part),
+            })
+            .next()
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("`{}` is not a valid MotorPort address", address),
+            })
+    }
+}
+
+/// Parses a BrickPi3 output port like `ME` to its layer and the port on the layer.
+fn parse_brickpi3_port(part: &str) -> Option<MotorPort> {
+    let mut chars = part.chars();
+    if chars.next()? != 'M' {
+        return None;
+    }
+    let letter = chars.next()?;
+    if chars.next().is_some() || !letter.is_ascii_uppercase() {
+        return None;
+    }
+
+    BrickPi3Port::from_index(u32::from(letter) - u32::from('A')).map(MotorPort::BrickPi3)
+}
+
+/// Behavior of a motor when the `stop` command is sent or a run command completes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum StopAction {
+    /// Removes power from the motor. The motor will freely coast to a stop.
+    Coast,
+    /// Removes power from the motor and creates a passive electrical load,
+    /// so the motor stops more quickly than coasting.
+    Brake,
+    /// Causes the motor to actively try to hold the current position.
+    Hold,
+}
+
+impl StopAction {
+    /// Returns the sysfs value of the stop action, e.g. `hold`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StopAction::Coast => "coast",
+            StopAction::Brake => "brake",
+            StopAction::Hold => "hold",
+        }
+    }
+}
+
+impl fmt::Display for StopAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for StopAction {
+    type Err = Ev3Error;
+
+    /// Parses a sysfs stop action like `hold`.
+    ///
+    /// Returns `Ev3Error::InternalError` for unknown stop actions.
+    fn from_str(stop_action: &str) -> Result<Self, Self::Err> {
+        match stop_action {
+            "coast" => Ok(StopAction::Coast),
+            "brake" => Ok(StopAction::Brake),
+            "hold" => Ok(StopAction::Hold),
+            _ => Err(Ev3Error::InternalError {
+                msg: format!("`{}` is not a valid StopAction", stop_action),
+            }),
+        }
+    }
+}
+
+/// Direction of rotation of a motor for positive setpoints.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Polarity {
+    /// A positive duty cycle will cause the motor to rotate clockwise.
+    Normal,
+    /// A positive duty cycle will cause the motor to rotate counter-clockwise.
+    Inversed,
+}
+
+impl Polarity {
+    /// Returns the sysfs value of the polarity, e.g. `inversed`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Polarity::Normal => "normal",
+            Polarity::Inversed => "inversed",
+        }
+    }
+
+    /// Returns the opposite polarity.
+    pub fn inverted(self) -> Polarity {
+        match self {
+            Polarity::Normal => Polarity::Inversed,
+            Polarity::Inversed => Polarity::Normal,
+        }
+    }
+}
+
+impl fmt::Display for Polarity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Polarity {
+    type Err = Ev3Error;
+
+    /// Parses a sysfs polarity like `inversed`.
+    ///
+    /// Returns `Ev3Error::InternalError` for unknown polarities.
+    fn from_str(polarity: &str) -> Result<Self, Self::Err> {
+        match polarity {
+            "normal" => Ok(Polarity::Normal),
+            "inversed" => Ok(Polarity::Inversed),
+            _ => Err(Ev3Error::InternalError {
+                msg: format!("`{}` is not a valid Polarity", polarity),
+            }),
         }
     }
 }
@@ -0,0 +1,164 @@
+//! Builder to configure several setpoints of a tacho motor at once.
+
+use std::time::Duration;
+
+use super::{Polarity, StopAction, TachoMotor};
+use crate::Ev3Result;
+
+/// Builder to configure several setpoints of a tacho motor at once, see `configure()` of the tacho motors.
+///
+/// Only the configured attributes are written. The writes are done in a fixed order:
+/// `polarity`, `stop_action`, `ramp_up_sp`, `ramp_down_sp`, `speed_sp`, `duty_cycle_sp`, `position_sp`, `time_sp`.
+/// Every write behaves like the corresponding setter of the motor.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{LargeMotor, StopAction};
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::find()?;
+///
+/// motor
+///     .configure()
+///     .speed_sp(400)
+///     .ramp_up(Duration::from_millis(200))
+///     .stop_action(StopAction::Hold)
+///     .apply()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "the configuration is only written by `apply` or `apply_all`"]
+pub struct MotorConfig {
+    motor: TachoMotor,
+    polarity: Option<Polarity>,
+    stop_action: Option<StopAction>,
+    ramp_up: Option<Duration>,
+    ramp_down: Option<Duration>,
+    speed_sp: Option<i32>,
+    duty_cycle_sp: Option<i32>,
+    position_sp: Option<i32>,
+    time_sp: Option<Duration>,
+}
+
+impl MotorConfig {
+    /// Create an empty configuration for the `motor`.
+    pub(crate) fn new(motor: TachoMotor) -> Self {
+        MotorConfig {
+            motor,
+            polarity: None,
+            stop_action: None,
+            ramp_up: None,
+            ramp_down: None,
+            speed_sp: None,
+            duty_cycle_sp: None,
+            position_sp: None,
+            time_sp: None,
+        }
+    }
+
+    /// Sets the polarity, see `set_polarity`.
+    pub fn polarity(mut self, polarity: Polarity) -> Self {
+        self.polarity = Some(polarity);
+        self
+    }
+
+    /// Sets the stop action, see `set_stop_action`.
+    pub fn stop_action(mut self, stop_action: StopAction) -> Self {
+        self.stop_action = Some(stop_action);
+        self
+    }
+
+    /// Sets the ramp up setpoint, see `set_ramp_up`.
+    pub fn ramp_up(mut self, ramp_up: Duration) -> Self {
+        self.ramp_up = Some(ramp_up);
+        self
+    }
+
+    /// Sets the ramp down setpoint, see `set_ramp_down`.
+    pub fn ramp_down(mut self, ramp_down: Duration) -> Self {
+        self.ramp_down = Some(ramp_down);
+        self
+    }
+
+    /// Sets the target speed in tacho counts per second, see `set_speed_sp`.
+    pub fn speed_sp(mut self, speed_sp: i32) -> Self {
+        self.speed_sp = Some(speed_sp);
+        self
+    }
+
+    /// Sets the duty cycle setpoint, see `set_duty_cycle_sp`.
+    pub fn duty_cycle_sp(mut self, duty_cycle_sp: i32) -> Self {
+        self.duty_cycle_sp = Some(duty_cycle_sp);
+        self
+    }
+
+    /// Sets the target position, see `set_position_sp`.
+    pub fn position_sp(mut self, position_sp: i32) -> Self {
+        self.position_sp = Some(position_sp);
+        self
+    }
+
+    /// Sets the run time of the `run-timed` command, see `set_time_sp`.
+    pub fn time_sp(mut self, time_sp: Duration) -> Self {
+        self.time_sp = Some(time_sp);
+        self
+    }
+
+    /// Writes all configured attributes and stops at the first error.
+    pub fn apply(self) -> Ev3Result<()> {
+        for write in self.writes() {
+            write(&self.motor)?;
+        }
+        Ok(())
+    }
+
+    /// Writes all configured attributes, even if some writes fail.
+    /// Returns the first error encountered.
+    pub fn apply_all(self) -> Ev3Result<()> {
+        let mut result = Ok(());
+        for write in self.writes() {
+            let write_result = write(&self.motor);
+            if result.is_ok() {
+                result = write_result;
+            }
+        }
+        result
+    }
+
+    /// Returns the configured writes in order.
+    fn writes(&self) -> Vec<Write> {
+        let mut writes: Vec<Write> = Vec::new();
+        if let Some(polarity) = self.polarity {
+            writes.push(Box::new(move |motor| motor.set_polarity(polarity)));
+        }
+        if let Some(stop_action) = self.stop_action {
+            writes.push(Box::new(move |motor| motor.set_stop_action(stop_action)));
+        }
+        if let Some(ramp_up) = self.ramp_up {
+            writes.push(Box::new(move |motor| motor.set_ramp_up(ramp_up)));
+        }
+        if let Some(ramp_down) = self.ramp_down {
+            writes.push(Box::new(move |motor| motor.set_ramp_down(ramp_down)));
+        }
+        if let Some(speed_sp) = self.speed_sp {
+            writes.push(Box::new(move |motor| motor.set_speed_sp(speed_sp)));
+        }
+        if let Some(duty_cycle_sp) = self.duty_cycle_sp {
+            writes.push(Box::new(move |motor| {
+                motor.set_duty_cycle_sp(duty_cycle_sp)
+            }));
+        }
+        if let Some(position_sp) = self.position_sp {
+            writes.push(Box::new(move |motor| motor.set_position_sp(position_sp)));
+        }
+        if let Some(time_sp) = self.time_sp {
+            writes.push(Box::new(move |motor| motor.set_time(time_sp)));
+        }
+        writes
+    }
+}
+
+/// A single attribute write of a `MotorConfig`.
+type Write = Box<dyn Fn(&TachoMotor) -> Ev3Result<()>>;
@@ -0,0 +1,199 @@
+//! A group of tacho motors that are started together, e.g. the wheels of a drive base.
+
+use std::time::Duration;
+
+use crate::{Attribute, Device, Ev3Error, Ev3Result};
+
+use super::TachoMotor;
+
+/// A group of tacho motors that are started together, e.g. the wheels of a drive base.
+///
+/// All setpoints are written before the first command is sent.
+/// The commands are then written back-to-back to minimize the start latency between the motors.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{MotorGroup, MotorPort, TachoMotor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let left = TachoMotor::get(MotorPort::OutB)?;
+/// let right = TachoMotor::get(MotorPort::OutC)?;
+///
+/// let drive = MotorGroup::new(vec![left, right]);
+///
+/// // Drive straight for one wheel rotation each.
+/// drive.set_speed_sp(&[500, 500])?;
+/// drive.run_to_rel_pos(&[360, 360])?;
+/// drive.wait_until_not_moving(None);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MotorGroup {
+    motors: Vec<TachoMotor>,
+    commands: Vec<Attribute>,
+}
+
+impl MotorGroup {
+    /// Create a new group of the given `motors`.
+    pub fn new(motors: Vec<TachoMotor>) -> MotorGroup {
+        let commands = motors
+            .iter()
+            .map(|motor| motor.get_attribute("command"))
+            .collect();
+
+        MotorGroup { motors, commands }
+    }
+
+    /// Returns the motors of this group.
+    pub fn motors(&self) -> &[TachoMotor] {
+        &self.motors
+    }
+
+    /// Checks that one value per motor is given.
+    fn check_len(&self, len: usize) -> Ev3Result<()> {
+        if len == self.motors.len() {
+            Ok(())
+        } else {
+            Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Expected {} values for the motor group, got {}",
+                    self.motors.len(),
+                    len
+                ),
+            })
+        }
+    }
+
+    /// Sends the `command` to all motors back-to-back.
+    fn run_command(&self, command: &str) -> Ev3Result<()> {
+        for attribute in &self.commands {
+            attribute.set_str_slice(command)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the target speed in tacho counts per second of each motor.
+    pub fn set_speed_sp(&self, speed_sp: &[i32]) -> Ev3Result<()> {
+        self.check_len(speed_sp.len())?;
+        for (motor, speed_sp) in self.motors.iter().zip(speed_sp) {
+            motor.set_speed_sp(*speed_sp)?;
+        }
+        Ok(())
+    }
+
+    /// Causes all motors to run until another command is sent.
+    pub fn run_forever(&self) -> Ev3Result<()> {
+        self.run_command(TachoMotor::COMMAND_RUN_FOREVER)
+    }
+
+    /// Runs all motors with the duty cycle of their `duty_cycle_sp`.
+    /// Changes of `duty_cycle_sp` take effect immediately, without another command.
+    pub fn run_direct(&self) -> Ev3Result<()> {
+        self.run_command(TachoMotor::COMMAND_RUN_DIRECT)
+    }
+
+    /// Runs each motor to the absolute position of `position_sp`.
+    /// All positions are written before the motors are started.
+    pub fn run_to_abs_pos(&self, position_sp: &[i32]) -> Ev3Result<()> {
+        self.check_len(position_sp.len())?;
+        for (motor, position_sp) in self.motors.iter().zip(position_sp) {
+            motor.set_position_sp(*position_sp)?;
+        }
+        self.run_command(TachoMotor::COMMAND_RUN_TO_ABS_POS)
+    }
+
+    /// Runs each motor to the position of `position_sp` relative to its current position.
+    /// All positions are written before the motors are started.
+    pub fn run_to_rel_pos(&self, position_sp: &[i32]) -> Ev3Result<()> {
+        self.check_len(position_sp.len())?;
+        for (motor, position_sp) in self.motors.iter().zip(position_sp) {
+            motor.set_position_sp(*position_sp)?;
+        }
+        self.run_command(TachoMotor::COMMAND_RUN_TO_REL_POS)
+    }
+
+    /// Runs all motors for the given `time_sp`.
+    pub fn run_timed(&self, time_sp: Duration) -> Ev3Result<()> {
+        for motor in &self.motors {
+            motor.set_time(time_sp)?;
+        }
+        self.run_command(TachoMotor::COMMAND_RUN_TIMED)
+    }
+
+    /// Returns the current position and speed in tacho counts of each motor, e.g. for the odometry of a drive base.
+    ///
+    /// All attributes are read uncached in a tight sequence, first the positions of all motors, then their speeds,
+    /// to minimize the time between the samples of the different motors.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::motors::{MotorGroup, MotorPort, TachoMotor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let left = TachoMotor::get(MotorPort::OutB)?;
+    /// let right = TachoMotor::get(MotorPort::OutC)?;
+    /// let drive = MotorGroup::new(vec![left, right]);
+    ///
+    /// let odometry = drive.get_odometry()?;
+    /// let (left_position, left_speed) = odometry[0];
+    /// let (right_position, right_speed) = odometry[1];
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_odometry(&self) -> Ev3Result<Vec<(i32, i32)>> {
+        let positions: Vec<Attribute> = self
+            .motors
+            .iter()
+            .map(|motor| motor.get_attribute("position"))
+            .collect();
+        let speeds: Vec<Attribute> = self
+            .motors
+            .iter()
+            .map(|motor| motor.get_attribute("speed"))
+            .collect();
+
+        let positions = positions
+            .iter()
+            .map(Attribute::get_uncached)
+            .collect::<Ev3Result<Vec<i32>>>()?;
+        let speeds = speeds
+            .iter()
+            .map(Attribute::get_uncached)
+            .collect::<Ev3Result<Vec<i32>>>()?;
+
+        Ok(positions.into_iter().zip(speeds).collect())
+    }
+
+    /// Stops all motors using the command specified by their `stop_action`.
+    /// A failing motor does not prevent the remaining motors from being stopped.
+    /// Returns the first error encountered.
+    pub fn stop(&self) -> Ev3Result<()> {
+        let mut result = Ok(());
+        for attribute in &self.commands {
+            let stop = attribute.set_str_slice(TachoMotor::COMMAND_STOP);
+            if result.is_ok() {
+                result = stop;
+            }
+        }
+        result
+    }
+
+    /// Wait until all motors are not moving anymore or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// Returns `false` if one of the motors is still moving after the `timeout`.
+    #[cfg(target_os = "linux")]
+    pub fn wait_until_not_moving(&self, timeout: Option<Duration>) -> bool {
+        let start = std::time::Instant::now();
+
+        for motor in &self.motors {
+            // An expired timeout still checks the current state of the remaining motors.
+            let remaining =
+                timeout.map(|timeout| timeout.checked_sub(start.elapsed()).unwrap_or_default());
+            if !motor.wait_until_not_moving(remaining) {
+                return false;
+            }
+        }
+        true
+    }
+}
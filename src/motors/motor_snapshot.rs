@@ -0,0 +1,52 @@
+//! Snapshot of the state and setpoints of a tacho motor.
+
+use super::{Polarity, StopAction};
+
+/// State and setpoints of a tacho motor, read once by `snapshot()` of the tacho motors.
+///
+/// With the `serde` feature the snapshot implements `Serialize` and `Deserialize`.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::LargeMotor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::find()?;
+///
+/// let snapshot = motor.snapshot()?;
+/// println!("Position {} at speed {}", snapshot.position, snapshot.speed);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct MotorSnapshot {
+    /// Name of the driver, e.g. `lego-ev3-l-motor`.
+    pub driver_name: String,
+    /// Address of the port, e.g. `ev3-ports:outA`.
+    pub address: String,
+    /// State flags, e.g. `running` and `ramping`.
+    pub state: Vec<String>,
+    /// Current position in tacho counts.
+    pub position: i32,
+    /// Current speed in tacho counts per second.
+    pub speed: i32,
+    /// Current duty cycle in percent.
+    pub duty_cycle: i32,
+    /// Direction of rotation for positive setpoints.
+    pub polarity: Polarity,
+    /// Behavior when a run command completes.
+    pub stop_action: StopAction,
+    /// Target speed in tacho counts per second.
+    pub speed_sp: i32,
+    /// Duty cycle setpoint in percent.
+    pub duty_cycle_sp: i32,
+    /// Target position in tacho counts.
+    pub position_sp: i32,
+    /// Run time of the `run-timed` command in milliseconds.
+    pub time_sp: i32,
+    /// Ramp up setpoint in milliseconds.
+    pub ramp_up_sp: i32,
+    /// Ramp down setpoint in milliseconds.
+    pub ramp_down_sp: i32,
+}
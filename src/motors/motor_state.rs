@@ -0,0 +1,138 @@
+//! State flags of a tacho motor.
+
+use std::fmt;
+use std::ops::BitOr;
+use std::str::FromStr;
+
+use crate::Ev3Error;
+
+/// Bit and sysfs name of every known state flag.
+const FLAGS: [(u8, &str); 5] = [
+    (1, "running"),
+    (1 << 1, "ramping"),
+    (1 << 2, "holding"),
+    (1 << 3, "overloaded"),
+    (1 << 4, "stalled"),
+];
+
+/// Set of state flags of the `state` attribute of a tacho motor.
+///
+/// Known flags are stored as bits, flags unknown to this crate are kept by name.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorState};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::find()?;
+///
+/// let state = motor.get_state()?;
+/// if state.contains(MotorState::RUNNING | MotorState::STALLED) {
+///     motor.stop()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct MotorState {
+    bits: u8,
+    unknown: Vec<String>,
+}
+
+impl MotorState {
+    /// Power is being sent to the motor.
+    pub const RUNNING: MotorState = MotorState::from_bits(1);
+
+    /// The motor is ramping up or down and has not yet reached a constant output level.
+    pub const RAMPING: MotorState = MotorState::from_bits(1 << 1);
+
+    /// The motor is not turning, but rather attempting to hold a fixed position.
+    pub const HOLDING: MotorState = MotorState::from_bits(1 << 2);
+
+    /// The motor is turning as fast as possible, but cannot reach its `speed_sp`.
+    pub const OVERLOADED: MotorState = MotorState::from_bits(1 << 3);
+
+    /// The motor is trying to run but is not turning at all.
+    pub const STALLED: MotorState = MotorState::from_bits(1 << 4);
+
+    /// State with only the known flags of `bits`.
+    const fn from_bits(bits: u8) -> MotorState {
+        MotorState {
+            bits,
+            unknown: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if all flags of `other` are set.
+    pub fn contains(&self, other: MotorState) -> bool {
+        self.bits & other.bits == other.bits
+            && other.unknown.iter().all(|flag| self.unknown.contains(flag))
+    }
+
+    /// Returns `true` if the flag with the sysfs `name`, e.g. `running`, is set.
+    /// Works for flags unknown to this crate as well.
+    pub fn contains_name(&self, name: &str) -> bool {
+        self.names().contains(&name)
+    }
+
+    /// Returns `true` if no flag is set.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0 && self.unknown.is_empty()
+    }
+
+    /// Returns the flags unknown to this crate by their sysfs name.
+    pub fn get_unknown(&self) -> &[String] {
+        &self.unknown
+    }
+
+    /// Returns the sysfs names of all set flags, known flags first.
+    pub fn names(&self) -> Vec<&str> {
+        FLAGS
+            .iter()
+            .filter(|&&(bit, _)| self.bits & bit != 0)
+            .map(|&(_, name)| name)
+            .chain(self.unknown.iter().map(String::as_str))
+            .collect()
+    }
+}
+
+impl BitOr for MotorState {
+    type Output = MotorState;
+
+    fn bitor(mut self, other: MotorState) -> MotorState {
+        self.bits |= other.bits;
+        for flag in other.unknown {
+            if !self.unknown.contains(&flag) {
+                self.unknown.push(flag);
+            }
+        }
+        self
+    }
+}
+
+impl fmt::Display for MotorState {
+    /// Formats the flags like the sysfs `state` attribute, separated by spaces.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.names().join(" "))
+    }
+}
+
+impl FromStr for MotorState {
+    type Err = Ev3Error;
+
+    /// Parses a space separated list of state flags like `running ramping`.
+    fn from_str(state: &str) -> Result<Self, Self::Err> {
+        let mut motor_state = MotorState::default();
+        for flag in state.split_whitespace() {
+            match FLAGS.iter().find(|&&(_, name)| name == flag) {
+                Some(&(bit, _)) => motor_state.bits |= bit,
+                None => {
+                    if !motor_state.unknown.iter().any(|unknown| unknown == flag) {
+                        motor_state.unknown.push(flag.to_owned());
+                    }
+                }
+            }
+        }
+        Ok(motor_state)
+    }
+}
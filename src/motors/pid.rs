@@ -0,0 +1,12 @@
+//! PID controller constants of a tacho motor.
+
+/// Constants of a PID controller of a tacho motor, see `get_hold_pid` and `get_speed_pid`.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Pid {
+    /// The proportional constant.
+    pub kp: f32,
+    /// The integral constant.
+    pub ki: f32,
+    /// The derivative constant.
+    pub kd: f32,
+}
@@ -0,0 +1,184 @@
+//! Software position controller for tacho motors.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::TachoMotor;
+use crate::{Ev3Error, Ev3Result};
+
+/// Interval between two steps of `run_to`.
+const TICK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Software position controller for a tacho motor, based on a PID controller.
+///
+/// The motor is run with the `run-direct` command. Every `step` reads the `position`
+/// and writes the resulting duty cycle to `duty_cycle_sp`, which takes effect immediately.
+/// In contrast to `run-to-abs-pos` the target can be changed at any time and a feedforward term can be added.
+///
+/// The gains map an error in tacho counts to a duty cycle in percent:
+/// `duty_cycle = kp * error + ki * integral(error) + kd * d(error)/dt + feedforward`,
+/// with the time in seconds. The duty cycle is limited to -100..=100.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{LargeMotor, PositionController};
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::find()?;
+///
+/// let mut controller = PositionController::new(motor.into(), 0.8, 0.1, 0.02);
+///
+/// // Drive the controller from a custom loop ...
+/// for target in (0..360).step_by(10) {
+///     controller.step(target)?;
+///     std::thread::sleep(Duration::from_millis(10));
+/// }
+///
+/// // ... or block until the target is reached.
+/// controller.run_to(0, 5, Some(Duration::from_secs(2)))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PositionController {
+    motor: TachoMotor,
+    kp: f32,
+    ki: f32,
+    kd: f32,
+    feedforward: f32,
+    integral: f32,
+    last_step: Option<(Instant, f32)>,
+}
+
+impl PositionController {
+    /// Create a new controller for the `motor` with the proportional gain `kp`,
+    /// the integral gain `ki` and the derivative gain `kd`.
+    pub fn new(motor: TachoMotor, kp: f32, ki: f32, kd: f32) -> Self {
+        PositionController {
+            motor,
+            kp,
+            ki,
+            kd,
+            feedforward: 0.0,
+            integral: 0.0,
+            last_step: None,
+        }
+    }
+
+    /// Returns the controlled motor.
+    pub fn motor(&self) -> &TachoMotor {
+        &self.motor
+    }
+
+    /// Sets the gains of the controller. The accumulated integral is kept.
+    pub fn set_gains(&mut self, kp: f32, ki: f32, kd: f32) {
+        self.kp = kp;
+        self.ki = ki;
+        self.kd = kd;
+    }
+
+    /// Sets a constant duty cycle in percent, that is added to the output of every step,
+    /// e.g. to compensate gravity or a constant load.
+    pub fn set_feedforward(&mut self, feedforward: f32) {
+        self.feedforward = feedforward;
+    }
+
+    /// Clears the accumulated integral and the last error.
+    /// The next step only uses the proportional and the feedforward term.
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_step = None;
+    }
+
+    /// Runs one control step towards the `target` position in tacho counts.
+    ///
+    /// Starts the motor with `run-direct` on the first step after creation or `reset`.
+    /// Returns the current error `target - position` in tacho counts.
+    pub fn step(&mut self, target: i32) -> Ev3Result<i32> {
+        let position = self.motor.get_position()?;
+        let now = Instant::now();
+        let error = target.saturating_sub(position);
+        let error_f = error as f32;
+
+        let (integral, derivative) = match self.last_step {
+            Some((last_time, last_error)) => {
+                let dt = now.duration_since(last_time).as_secs_f32();
+                if dt > 0.0 {
+                    (self.integral + error_f * dt, (error_f - last_error) / dt)
+                } else {
+                    (self.integral, 0.0)
+                }
+            }
+            None => (self.integral, 0.0),
+        };
+
+        let output =
+            self.kp * error_f + self.ki * integral + self.kd * derivative + self.feedforward;
+        let duty_cycle = output.clamp(-100.0, 100.0);
+
+        // Anti-windup: only accumulate the error while the output is not saturated.
+        if output == duty_cycle {
+            self.integral = integral;
+        }
+
+        let start = self.last_step.is_none();
+        self.last_step = Some((now, error_f));
+
+        self.motor.set_duty_cycle_sp(duty_cycle.round() as i32)?;
+        if start {
+            self.motor.run_direct()?;
+        }
+
+        Ok(error)
+    }
+
+    /// Runs the controller until the position is within `tolerance` tacho counts of the `target`
+    /// and stops the motor using the command specified in `stop_action`.
+    ///
+    /// A step is run every 10 milliseconds.
+    /// Returns the final position of the motor.
+    /// Returns `Ev3Error::Timeout` after stopping the motor if the target is not reached within the `timeout`.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// The motor is also stopped if a step fails, and the error of the step is returned.
+    pub fn run_to(
+        &mut self,
+        target: i32,
+        tolerance: i32,
+        timeout: Option<Duration>,
+    ) -> Ev3Result<i32> {
+        let start = Instant::now();
+
+        loop {
+            let error = match self.step(target) {
+                Ok(error) => error,
+                Err(e) => {
+                    self.stop().ok();
+                    return Err(e);
+                }
+            };
+            if error.saturating_abs() <= tolerance {
+                break;
+            }
+            if let Some(timeout) = timeout {
+                if start.elapsed() >= timeout {
+                    self.stop()?;
+                    return Err(Ev3Error::Timeout);
+                }
+            }
+            thread::sleep(TICK_INTERVAL);
+        }
+
+        self.stop()?;
+        self.motor.get_position()
+    }
+
+    /// Stops the motor using the command specified in `stop_action` and resets the controller.
+    /// The `stop` command is sent before the duty cycle is reset, so a failed reset does not keep the motor running.
+    pub fn stop(&mut self) -> Ev3Result<()> {
+        self.reset();
+        let stop = self.motor.stop();
+        self.motor.set_duty_cycle_sp(0)?;
+        stop
+    }
+}
@@ -0,0 +1,39 @@
+use crate::sensors::SensorPort;
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+/// Hobby type servo motor, e.g. connected to a servo controller at an input port.
+///
+/// Servo controllers expose their motors as channels of the controller address
+/// like `ev3-ports:in1:i2c88:sv1`, so use `get_on_channel` to select a motor.
+///
+/// ```no_run
+/// use ev3dev_lang_rust::motors::ServoMotor;
+/// use ev3dev_lang_rust::sensors::SensorPort;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let servo = ServoMotor::get_on_channel(SensorPort::In1, "i2c88:sv1")?;
+///
+/// servo.set_position_sp(50)?;
+/// servo.run()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device)]
+pub struct ServoMotor {
+    driver: Driver,
+}
+
+impl ServoMotor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!(
+        "servo-motor",
+        ["servo-motor"],
+        SensorPort,
+        "ServoMotor",
+        "in"
+    );
+    servo_motor!();
+}
@@ -4,10 +4,10 @@
 #[macro_export]
 macro_rules! servo_motor {
     () => {
-        /// Remove power from the motor.
+        /// Drive servo to the position set in the position_sp attribute.
         pub const COMMAND_RUN: &'static str = "run";
 
-        /// Drive servo to the position set in the position_sp attribute.
+        /// Remove power from the motor.
         pub const COMMAND_FLOAT: &'static str = "float";
 
         /// With normal polarity, a positive duty cycle will cause the motor to rotate clockwise.
@@ -20,13 +20,22 @@ macro_rules! servo_motor {
         pub const STATE_RUNNING: &'static str = "running";
 
         /// Returns the current polarity of the motor.
-        pub fn get_polarity(&self) -> Ev3Result<String> {
+        pub fn get_polarity(&self) -> Ev3Result<$crate::motors::Polarity> {
             self.get_attribute("polarity").get()
         }
 
         /// Sets the polarity of the motor.
-        pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
-            self.get_attribute("polarity").set_str_slice(polarity)
+        pub fn set_polarity(&self, polarity: $crate::motors::Polarity) -> Ev3Result<()> {
+            self.get_attribute("polarity")
+                .set_str_slice(polarity.as_str())
+        }
+
+        /// Inverts the current polarity of the motor, e.g. for a wheel that is mounted backwards.
+        /// Returns the new polarity.
+        pub fn toggle_polarity(&self) -> Ev3Result<$crate::motors::Polarity> {
+            let polarity = self.get_polarity()?.inverted();
+            self.set_polarity(polarity)?;
+            Ok(polarity)
         }
 
         /// Returns the current max pulse setpoint.
@@ -0,0 +1,174 @@
+//! Stopping motors when the program ends or is interrupted.
+
+use std::sync::atomic::{AtomicI32, Ordering};
+
+use super::motor_commands;
+use crate::{Attribute, Device, Ev3Result};
+
+/// The `stop` command written by the guards.
+const STOP: &str = "stop";
+
+/// Maximal number of motors that are stopped by the SIGINT handler of `run_with_graceful_shutdown`.
+const MAX_SIGINT_MOTORS: usize = 16;
+
+/// Initial value of the `SIGINT_FDS` slots.
+#[allow(clippy::declare_interior_mutable_const)]
+const UNUSED_FD: AtomicI32 = AtomicI32::new(-1);
+
+/// File descriptors of the `command` attributes written by the SIGINT handler, `-1` for unused slots.
+static SIGINT_FDS: [AtomicI32; MAX_SIGINT_MOTORS] = [UNUSED_FD; MAX_SIGINT_MOTORS];
+
+/// Stops a set of motors when it is dropped, e.g. at the end of a scope or while unwinding from a panic.
+///
+/// Each motor stops with its current `stop_action`, `coast` by default.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort, MotorStopGuard};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let left = LargeMotor::get(MotorPort::OutA)?;
+/// let right = LargeMotor::get(MotorPort::OutB)?;
+///
+/// let mut guard = MotorStopGuard::new();
+/// guard.add(&left);
+/// guard.add(&right);
+///
+/// left.run_forever()?;
+/// right.run_forever()?;
+///
+/// // Both motors are stopped when `guard` goes out of scope, even if this returns early.
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+#[must_use = "the motors are stopped when the guard is dropped"]
+pub struct MotorStopGuard {
+    commands: Vec<Attribute>,
+}
+
+impl MotorStopGuard {
+    /// Create a guard without motors.
+    pub fn new() -> Self {
+        MotorStopGuard::default()
+    }
+
+    /// Create a guard for all currently connected tacho and dc motors, see `stop_all`.
+    /// Motors that can not be opened are skipped.
+    pub fn all() -> Self {
+        MotorStopGuard {
+            commands: motor_commands()
+                .into_iter()
+                .filter_map(Result::ok)
+                .collect(),
+        }
+    }
+
+    /// Adds the `motor` to the guarded motors.
+    pub fn add(&mut self, motor: &dyn Device) {
+        self.commands.push(motor.get_attribute("command"));
+    }
+
+    /// Stops all guarded motors now. A failing motor does not prevent the remaining motors from being stopped.
+    /// Returns the first error encountered.
+    pub fn stop(&self) -> Ev3Result<()> {
+        let mut result = Ok(());
+        for command in &self.commands {
+            let stop = command.set_str(STOP);
+            if result.is_ok() {
+                result = stop;
+            }
+        }
+        result
+    }
+}
+
+impl Drop for MotorStopGuard {
+    fn drop(&mut self) {
+        self.stop().ok();
+    }
+}
+
+/// Runs `f` and stops all tacho and dc motors afterwards, also if `f` panics or the program receives SIGINT (Ctrl-C).
+///
+/// The motors that are connected at the start are guarded by a `MotorStopGuard`.
+/// While `f` runs, a SIGINT handler is installed, that writes the `stop` command to the motors
+/// and then terminates the program with the default SIGINT behavior. The previous SIGINT handler is restored when `f` returns.
+///
+/// The handler only uses async-signal-safe calls and a fixed table of file descriptors,
+/// so on SIGINT only the first 16 connected motors are stopped and further motors keep running.
+/// The `MotorStopGuard` on return or panic is not limited and stops all motors.
+///
+/// Must not be nested or called from several threads at once.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{self, LargeMotor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// motors::run_with_graceful_shutdown(|| -> ev3dev_lang_rust::Ev3Result<()> {
+///     let motor = LargeMotor::find()?;
+///     motor.run_forever()?;
+///
+///     // Ctrl-C stops the motor.
+///     loop {}
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn run_with_graceful_shutdown<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let guard = MotorStopGuard::all();
+    // Declared after `guard`, so it is dropped first and the handler never writes to closed file descriptors.
+    let _handler = SigintHandler::install(&guard.commands);
+
+    f()
+}
+
+/// Installed SIGINT handler of `run_with_graceful_shutdown`, restores the previous handler on drop.
+struct SigintHandler {
+    previous: libc::sighandler_t,
+}
+
+impl SigintHandler {
+    /// Registers the `commands` for the handler and installs it.
+    fn install(commands: &[Attribute]) -> SigintHandler {
+        for (slot, command) in SIGINT_FDS.iter().zip(commands) {
+            slot.store(command.get_raw_fd(), Ordering::SeqCst);
+        }
+
+        let handler = handle_sigint as extern "C" fn(libc::c_int);
+        let previous = unsafe { libc::signal(libc::SIGINT, handler as libc::sighandler_t) };
+        SigintHandler { previous }
+    }
+}
+
+impl Drop for SigintHandler {
+    fn drop(&mut self) {
+        unsafe {
+            libc::signal(libc::SIGINT, self.previous);
+        }
+        for slot in &SIGINT_FDS {
+            slot.store(-1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Writes `stop` to all registered motors and re-raises the signal with the default handler.
+extern "C" fn handle_sigint(signal: libc::c_int) {
+    for slot in &SIGINT_FDS {
+        let fd = slot.load(Ordering::SeqCst);
+        if fd >= 0 {
+            unsafe {
+                libc::pwrite(fd, STOP.as_ptr() as *const libc::c_void, STOP.len(), 0);
+            }
+        }
+    }
+
+    unsafe {
+        libc::signal(signal, libc::SIG_DFL);
+        libc::raise(signal);
+    }
+}
@@ -4,9 +4,12 @@
 
 use std::time::Duration;
 
-use crate::{Ev3Error, Ev3Result};
+use crate::{Attribute, Device, Ev3Error, Ev3Result, Port};
 
-use super::{LargeMotor, MediumMotor, MotorPort};
+use super::{
+    CountsPerSec, IntoCounts, LargeMotor, MediumMotor, MotorConfig, MotorPort, MotorSnapshot,
+    MotorState, Pid, Polarity, StopAction,
+};
 
 #[derive(Debug, Clone)]
 enum TachoMotorInner {
@@ -45,7 +48,7 @@ impl TachoMotor {
         })
     }
 
-    /// Try to find a `Self`. Only returns a motor if their is exactly one connected, `Error::NotFound` otherwise.
+    /// Try to find a `Self`. Only returns a motor if their is exactly one connected, `Ev3Error::NotConnected` otherwise.
     pub fn find() -> Ev3Result<Self> {
         let large_motor = LargeMotor::find();
         if let Ok(motor) = large_motor {
@@ -66,7 +69,20 @@ impl TachoMotor {
         })
     }
 
-    /// Extract list of connected 'Self'
+    /// Extract list of all connected large and medium motors, ordered by port address.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::motors::TachoMotor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// // Stop all motors.
+    /// for motor in TachoMotor::list()? {
+    ///     motor.stop()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub fn list() -> Ev3Result<Vec<Self>> {
         let large_motor = LargeMotor::list()?;
         let medium_motor = MediumMotor::list()?;
@@ -81,10 +97,14 @@ impl TachoMotor {
             inner: TachoMotorInner::MediumMotor { motor },
         }));
 
+        vec.sort_by_cached_key(|motor| {
+            crate::driver::address_order(&motor.get_address().unwrap_or_default())
+        });
         Ok(vec)
     }
 
     /// Try to convert this tacho motor to an `LargeMotor`, return `Self` if this fails.
+    #[allow(clippy::result_large_err)]
     pub fn into_large_motor(self) -> Result<LargeMotor, TachoMotor> {
         match self.inner {
             TachoMotorInner::LargeMotor { motor } => Ok(motor),
@@ -92,7 +112,8 @@ impl TachoMotor {
         }
     }
 
-    /// Try to convert this tacho motor to an `LargeMotor`, return `Self` if this fails.
+    /// Try to convert this tacho motor to an `MediumMotor`, return `Self` if this fails.
+    #[allow(clippy::result_large_err)]
     pub fn into_medium_motor(self) -> Result<MediumMotor, TachoMotor> {
         match self.inner {
             inner @ TachoMotorInner::LargeMotor { motor: _ } => Err(TachoMotor { inner }),
@@ -127,6 +148,9 @@ impl TachoMotor {
     /// This will also have the effect of stopping the motor.
     pub const COMMAND_RESET: &'static str = "reset";
 
+    /// Maximal value of `ramp_up_sp` and `ramp_down_sp` in milliseconds accepted by the kernel.
+    pub const MAX_RAMP_SP: i32 = 60_000;
+
     /// A positive duty cycle will cause the motor to rotate clockwise.
     pub const POLARITY_NORMAL: &'static str = "normal";
 
@@ -206,6 +230,34 @@ impl TachoMotor {
         }
     }
 
+    /// Returns the cached `max_speed` attribute, `None` if it could not be read yet.
+    ///
+    /// The constant attributes are read when the motor is constructed (e.g. by `find`) or reconnected
+    /// and are never changed by the driver, not even by the `reset` command.
+    pub fn max_speed(&self) -> Option<i32> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.max_speed(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.max_speed(),
+        }
+    }
+
+    /// Returns the cached `count_per_rot` attribute, `None` if it could not be read yet (see `max_speed()`).
+    pub fn count_per_rot(&self) -> Option<i32> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.count_per_rot(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.count_per_rot(),
+        }
+    }
+
+    /// Returns the cached `count_per_m` attribute, `None` if it could not be read yet (see `max_speed()`).
+    /// (linear motors only)
+    pub fn count_per_m(&self) -> Option<i32> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.count_per_m(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.count_per_m(),
+        }
+    }
+
     /// Returns the number of tacho counts in the full travel of the motor.
     ///
     /// When combined with the count_per_m atribute,
@@ -284,6 +336,8 @@ impl TachoMotor {
     ///
     /// Units are in percent.
     /// Valid values are -100 to 100. A negative value causes the motor to rotate in reverse.
+    /// Values outside of this range are clamped.
+    /// While the motor runs with `run-direct`, the new duty cycle takes effect immediately.
     ///
     /// # Examples
     ///
@@ -316,7 +370,7 @@ impl TachoMotor {
     }
 
     /// Returns the current polarity of the motor.
-    pub fn get_polarity(&self) -> Ev3Result<String> {
+    pub fn get_polarity(&self) -> Ev3Result<Polarity> {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.get_polarity(),
             TachoMotorInner::MediumMotor { ref motor } => motor.get_polarity(),
@@ -324,13 +378,22 @@ impl TachoMotor {
     }
 
     /// Sets the polarity of the motor.
-    pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
+    pub fn set_polarity(&self, polarity: Polarity) -> Ev3Result<()> {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.set_polarity(polarity),
             TachoMotorInner::MediumMotor { ref motor } => motor.set_polarity(polarity),
         }
     }
 
+    /// Inverts the current polarity of the motor, e.g. for a wheel that is mounted backwards.
+    /// Returns the new polarity.
+    pub fn toggle_polarity(&self) -> Ev3Result<Polarity> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.toggle_polarity(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.toggle_polarity(),
+        }
+    }
+
     /// Returns the current position of the motor in pulses of the rotary encoder.
     ///
     /// When the motor rotates clockwise, the position will increase.
@@ -443,6 +506,24 @@ impl TachoMotor {
         }
     }
 
+    /// Returns all constants of the position PID.
+    /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+    pub fn get_hold_pid(&self) -> Ev3Result<Pid> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_hold_pid(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_hold_pid(),
+        }
+    }
+
+    /// Sets all constants of the position PID.
+    /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+    pub fn set_hold_pid(&self, pid: Pid) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.set_hold_pid(pid),
+            TachoMotorInner::MediumMotor { ref motor } => motor.set_hold_pid(pid),
+        }
+    }
+
     /// Returns the maximum value that is accepted by the `speed_sp` attribute.
     ///
     /// This value is the speed of the motor at 9V with no load.
@@ -455,6 +536,57 @@ impl TachoMotor {
         }
     }
 
+    /// Returns the current position of the motor in degrees.
+    /// The cached `count_per_rot` attribute is used, see `count_per_rot()`.
+    pub fn get_position_degrees(&self) -> Ev3Result<f32> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_position_degrees(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_position_degrees(),
+        }
+    }
+
+    /// Runs the motor by the given amount of `degrees` relative to the current position,
+    /// see `run_to_rel_pos`. A negative value rotates the motor backwards.
+    /// The cached `count_per_rot` attribute is used, see `count_per_rot()`.
+    pub fn run_to_rel_degrees(&self, degrees: f32) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.run_to_rel_degrees(degrees),
+            TachoMotorInner::MediumMotor { ref motor } => motor.run_to_rel_degrees(degrees),
+        }
+    }
+
+    /// Runs the motor by the given amount of `rotations` relative to the current position,
+    /// see `run_to_rel_pos`. A negative value rotates the motor backwards.
+    /// The cached `count_per_rot` attribute is used, see `count_per_rot()`.
+    pub fn run_to_rel_rotations(&self, rotations: f32) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.run_to_rel_rotations(rotations),
+            TachoMotorInner::MediumMotor { ref motor } => motor.run_to_rel_rotations(rotations),
+        }
+    }
+
+    /// Sets the target speed as percentage of `max_speed` used for all run-* commands except run-direct.
+    ///
+    /// The `percent` is clamped to the range [-100, 100], so the `speed_sp` never exceeds `max_speed`.
+    /// A negative value causes the motor to rotate in reverse
+    /// with the exception of run-to-*-pos commands where the sign is ignored.
+    /// The cached `max_speed` attribute is used, see `max_speed()`.
+    pub fn set_speed_sp_percent(&self, percent: f32) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.set_speed_sp_percent(percent),
+            TachoMotorInner::MediumMotor { ref motor } => motor.set_speed_sp_percent(percent),
+        }
+    }
+
+    /// Returns the current motor speed as percentage of `max_speed`.
+    /// The cached `max_speed` attribute is used, see `max_speed()`.
+    pub fn get_speed_percent(&self) -> Ev3Result<f32> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_speed_percent(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_speed_percent(),
+        }
+    }
+
     /// Returns the current target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
     ///
     /// Units are in tacho counts.
@@ -470,8 +602,8 @@ impl TachoMotor {
 
     /// Sets the target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
     ///
-    /// Units are in tacho counts.
-    /// You can use the value returned by `counts_per_rot` to convert tacho counts to/from rotations or degrees.
+    /// Plain numbers are in tacho counts. `Degrees` and `Rotations` are converted
+    /// with the cached `count_per_rot` attribute, see `IntoCounts`.
     ///
     /// The range is -2,147,483,648 and +2,147,483,647 tachometer counts (32-bit signed integer).
     ///
@@ -502,7 +634,10 @@ impl TachoMotor {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_position_sp(&self, position_sp: i32) -> Ev3Result<()> {
+    pub fn set_position_sp<P>(&self, position_sp: P) -> Ev3Result<()>
+    where
+        P: IntoCounts,
+    {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.set_position_sp(position_sp),
             TachoMotorInner::MediumMotor { ref motor } => motor.set_position_sp(position_sp),
@@ -520,6 +655,17 @@ impl TachoMotor {
         }
     }
 
+    /// Returns the current position and speed in tacho counts, read back-to-back.
+    ///
+    /// Both attributes are read uncached and directly after each other, see `Attribute::get_uncached`,
+    /// to minimize the time between the two samples, e.g. for odometry.
+    pub fn get_position_and_speed(&self) -> Ev3Result<(i32, i32)> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_position_and_speed(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_position_and_speed(),
+        }
+    }
+
     /// Returns the target speed in tacho counts per second used for all run-* commands except run-direct.
     ///
     /// A negative value causes the motor to rotate in reverse
@@ -539,7 +685,10 @@ impl TachoMotor {
     /// with the exception of run-to-*-pos commands where the sign is ignored.
     /// Use the `count_per_rot` attribute to convert RPM or deg/sec to tacho counts per second.
     /// Use the `count_per_m` attribute to convert m/s to tacho counts per second.
-    pub fn set_speed_sp(&self, speed_sp: i32) -> Ev3Result<()> {
+    pub fn set_speed_sp<S>(&self, speed_sp: S) -> Ev3Result<()>
+    where
+        S: Into<CountsPerSec>,
+    {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.set_speed_sp(speed_sp),
             TachoMotorInner::MediumMotor { ref motor } => motor.set_speed_sp(speed_sp),
@@ -572,6 +721,24 @@ impl TachoMotor {
         }
     }
 
+    /// Returns the current ramp up setpoint as `Duration`, see `get_ramp_up_sp`.
+    pub fn get_ramp_up(&self) -> Ev3Result<Duration> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_ramp_up(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_ramp_up(),
+        }
+    }
+
+    /// Sets the ramp up setpoint from a `Duration`, see `set_ramp_up_sp`.
+    ///
+    /// The kernel rejects ramp setpoints above 60 seconds, so longer durations are clamped to 60 seconds.
+    pub fn set_ramp_up(&self, ramp_up: Duration) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.set_ramp_up(ramp_up),
+            TachoMotorInner::MediumMotor { ref motor } => motor.set_ramp_up(ramp_up),
+        }
+    }
+
     /// Returns the current ramp down setpoint.
     ///
     /// Units are in milliseconds and must be positive. When set to a non-zero value,
@@ -598,6 +765,42 @@ impl TachoMotor {
         }
     }
 
+    /// Returns the current ramp down setpoint as `Duration`, see `get_ramp_down_sp`.
+    pub fn get_ramp_down(&self) -> Ev3Result<Duration> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_ramp_down(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_ramp_down(),
+        }
+    }
+
+    /// Sets the ramp down setpoint from a `Duration`, see `set_ramp_down_sp`.
+    ///
+    /// The kernel rejects ramp setpoints above 60 seconds, so longer durations are clamped to 60 seconds.
+    pub fn set_ramp_down(&self, ramp_down: Duration) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.set_ramp_down(ramp_down),
+            TachoMotorInner::MediumMotor { ref motor } => motor.set_ramp_down(ramp_down),
+        }
+    }
+
+    /// Returns all constants of the speed regulation PID.
+    /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+    pub fn get_speed_pid(&self) -> Ev3Result<Pid> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_speed_pid(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_speed_pid(),
+        }
+    }
+
+    /// Sets all constants of the speed regulation PID.
+    /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+    pub fn set_speed_pid(&self, pid: Pid) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.set_speed_pid(pid),
+            TachoMotorInner::MediumMotor { ref motor } => motor.set_speed_pid(pid),
+        }
+    }
+
     /// Returns the proportional pub constant for the speed regulation PID.
     pub fn get_speed_pid_kp(&self) -> Ev3Result<f32> {
         match self.inner {
@@ -646,8 +849,11 @@ impl TachoMotor {
         }
     }
 
-    /// Returns a list of state flags.
-    pub fn get_state(&self) -> Ev3Result<Vec<String>> {
+    /// Returns the state flags, see `MotorState`.
+    ///
+    /// Use `is_running()`, `is_ramping()`, `is_holding()`, `is_overloaded()` and `is_stalled()`
+    /// to check for a single flag without blocking.
+    pub fn get_state(&self) -> Ev3Result<MotorState> {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.get_state(),
             TachoMotorInner::MediumMotor { ref motor } => motor.get_state(),
@@ -657,7 +863,7 @@ impl TachoMotor {
     /// Returns the current stop action.
     ///
     /// The value determines the motors behavior when command is set to stop.
-    pub fn get_stop_action(&self) -> Ev3Result<String> {
+    pub fn get_stop_action(&self) -> Ev3Result<StopAction> {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.get_stop_action(),
             TachoMotorInner::MediumMotor { ref motor } => motor.get_stop_action(),
@@ -667,7 +873,9 @@ impl TachoMotor {
     /// Sets the stop action.
     ///
     /// The value determines the motors behavior when command is set to stop.
-    pub fn set_stop_action(&self, stop_action: &str) -> Ev3Result<()> {
+    /// Returns `Ev3Error::InternalError` if the motor controller does not support the `stop_action`,
+    /// see `get_stop_actions()`.
+    pub fn set_stop_action(&self, stop_action: StopAction) -> Ev3Result<()> {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.set_stop_action(stop_action),
             TachoMotorInner::MediumMotor { ref motor } => motor.set_stop_action(stop_action),
@@ -682,6 +890,14 @@ impl TachoMotor {
         }
     }
 
+    /// Returns the supported stop actions of the motor controller, unknown stop actions are skipped.
+    pub fn get_available_stop_actions(&self) -> Ev3Result<Vec<StopAction>> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_available_stop_actions(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_available_stop_actions(),
+        }
+    }
+
     /// Returns the current amount of time the motor will run when using the run-timed command.
     ///
     /// Units are in milliseconds. Values must not be negative.
@@ -702,9 +918,51 @@ impl TachoMotor {
         }
     }
 
+    /// Sets the run time of the run-timed command from a `Duration`, see `set_time_sp`.
+    ///
+    /// Durations longer than `i32::MAX` milliseconds are clamped.
+    pub fn set_time(&self, time: Duration) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.set_time(time),
+            TachoMotorInner::MediumMotor { ref motor } => motor.set_time(time),
+        }
+    }
+
+    /// Returns the commands supported by the motor, e.g. `run-forever` or `stop`.
+    /// The constant `commands` attribute is read once and cached afterwards.
+    pub fn get_available_commands(&self) -> Ev3Result<Vec<String>> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_available_commands(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_available_commands(),
+        }
+    }
+
+    /// Sends the `command` to the motor. All run commands are sent this way.
+    ///
+    /// Returns `Ev3Error::InternalError` if the command is not listed in `get_available_commands()`,
+    /// instead of the `EINVAL` of the kernel. Errors of reading the `commands` attribute are returned
+    /// and no command is sent. Use `send_command_unchecked()` to skip this validation.
+    pub fn send_command(&self, command: &str) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.send_command(command),
+            TachoMotorInner::MediumMotor { ref motor } => motor.send_command(command),
+        }
+    }
+
+    /// Sends the `command` to the motor without checking it against the list of supported commands.
+    /// This can be used to force undocumented commands or motors without a readable `commands` attribute.
+    /// `stop` and `reset` are sent this way, so stopping a motor never depends on reading `commands`.
+    pub fn send_command_unchecked(&self, command: &str) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.send_command_unchecked(command),
+            TachoMotorInner::MediumMotor { ref motor } => motor.send_command_unchecked(command),
+        }
+    }
+
     /// Runs the motor using the duty cycle specified by `duty_cycle_sp`.
     ///
     /// Unlike other run commands, changing `duty_cycle_sp` while running will take effect immediately.
+    /// The duty cycle is applied open loop: `run-direct` bypasses `ramp_up_sp`, `ramp_down_sp` and the speed PID.
     pub fn run_direct(&self) -> Ev3Result<()> {
         match self.inner {
             TachoMotorInner::LargeMotor { ref motor } => motor.run_direct(),
@@ -730,6 +988,56 @@ impl TachoMotor {
         }
     }
 
+    /// Runs the motor to the absolute `position`, see `run_to_abs_pos`.
+    /// The `position` can be given in any unit of `IntoCounts`, e.g. `Rotations`.
+    pub fn run_to_abs<P>(&self, position: P) -> Ev3Result<()>
+    where
+        P: IntoCounts,
+    {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.run_to_abs(position),
+            TachoMotorInner::MediumMotor { ref motor } => motor.run_to_abs(position),
+        }
+    }
+
+    /// Runs the motor to the absolute position `position_sp` and waits until the motor has stopped.
+    ///
+    /// Returns the final position of the motor after the motor stopped using the command specified in `stop_action`.
+    /// Returns `Ev3Error::Timeout` if the motor is still running after the `timeout`, e.g. because it is stalled.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::motors::TachoMotor;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// // Init a tacho motor.
+    /// let motor = TachoMotor::find()?;
+    ///
+    /// let position = motor.run_to_abs_pos_blocking(360, Some(Duration::from_secs(5)))?;
+    ///
+    /// println!("Motor stopped at position {}", position);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn run_to_abs_pos_blocking(
+        &self,
+        position_sp: i32,
+        timeout: Option<Duration>,
+    ) -> Ev3Result<i32> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => {
+                motor.run_to_abs_pos_blocking(position_sp, timeout)
+            }
+            TachoMotorInner::MediumMotor { ref motor } => {
+                motor.run_to_abs_pos_blocking(position_sp, timeout)
+            }
+        }
+    }
+
     /// Runs the motor to a position relative to the current position value.
     ///
     /// The new position will be current `position` + `position_sp`.
@@ -741,6 +1049,18 @@ impl TachoMotor {
         }
     }
 
+    /// Runs the motor to the `position` relative to the current position, see `run_to_rel_pos`.
+    /// The `position` can be given in any unit of `IntoCounts`, e.g. `Degrees`.
+    pub fn run_to_rel<P>(&self, position: P) -> Ev3Result<()>
+    where
+        P: IntoCounts,
+    {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.run_to_rel(position),
+            TachoMotorInner::MediumMotor { ref motor } => motor.run_to_rel(position),
+        }
+    }
+
     /// Run the motor for the amount of time specified in `time_sp`
     ///
     /// and then stops the motor using the command specified by `stop_action`.
@@ -751,6 +1071,18 @@ impl TachoMotor {
         }
     }
 
+    /// Runs the motor for the given `time_sp` and waits until the motor has stopped
+    /// using the command specified by `stop_action`.
+    ///
+    /// The state is awaited with the `wait` module, see `wait_until_not_moving`.
+    #[cfg(target_os = "linux")]
+    pub fn run_timed_blocking(&self, time_sp: Duration) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.run_timed_blocking(time_sp),
+            TachoMotorInner::MediumMotor { ref motor } => motor.run_timed_blocking(time_sp),
+        }
+    }
+
     /// Stop any of the run commands before they are complete using the command specified by `stop_action`.
     pub fn stop(&self) -> Ev3Result<()> {
         match self.inner {
@@ -768,6 +1100,35 @@ impl TachoMotor {
         }
     }
 
+    /// Returns a builder to configure several setpoints at once, see `MotorConfig`.
+    pub fn configure(&self) -> MotorConfig {
+        MotorConfig::new(self.clone())
+    }
+
+    /// Reads the state and setpoints of the motor once, see `MotorSnapshot`.
+    pub fn snapshot(&self) -> Ev3Result<MotorSnapshot> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.snapshot(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.snapshot(),
+        }
+    }
+
+    /// Resets the motor with the `reset` command and reads back the constant motor attributes.
+    ///
+    /// The kernel `reset` command already stops the motor, sets `position` to 0 and restores
+    /// `speed_sp`, `duty_cycle_sp`, `position_sp`, `time_sp`, `ramp_up_sp`, `ramp_down_sp`, `stop_action`,
+    /// `polarity` and the hold and speed PID constants to their driver defaults.
+    /// Additionally this refreshes the cached `max_speed`, `count_per_rot` and `count_per_m`
+    /// and checks that `count_per_rot` and `max_speed` are readable.
+    ///
+    /// Values cached by `Attribute::set_if_changed` are not aware of the reset.
+    pub fn reset_to_defaults(&self) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.reset_to_defaults(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.reset_to_defaults(),
+        }
+    }
+
     /// Power is being sent to the motor.
     pub fn is_running(&self) -> Ev3Result<bool> {
         match self.inner {
@@ -808,6 +1169,29 @@ impl TachoMotor {
         }
     }
 
+    /// Detects a stall from the actual `speed` compared to the `speed_sp`,
+    /// independent of the `stalled` state flag of the motor driver.
+    ///
+    /// The speed is sampled every 10 milliseconds during the `window`.
+    /// Returns `true` if the motor is running and the absolute `speed` stays below
+    /// `min_speed_ratio` times the absolute `speed_sp` for every sample of the `window`.
+    /// Returns `false` as soon as one sample is fast enough or the motor is not running,
+    /// so a moving motor is detected after a single sample.
+    ///
+    /// A zero `window` takes a single sample without sleeping, which is cheap enough
+    /// to be used as condition of `wait`. The `speed_sp` is not used by the `run-direct` command.
+    /// Note that the motor also runs slower than `speed_sp` while it accelerates after a start.
+    pub fn detect_stall(&self, min_speed_ratio: f32, window: Duration) -> Ev3Result<bool> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => {
+                motor.detect_stall(min_speed_ratio, window)
+            }
+            TachoMotorInner::MediumMotor { ref motor } => {
+                motor.detect_stall(min_speed_ratio, window)
+            }
+        }
+    }
+
     /// Wait until condition `cond` returns true or the `timeout` is reached.
     ///
     /// The condition is checked when to the `state` attribute has changed.
@@ -816,7 +1200,7 @@ impl TachoMotor {
     /// # Examples
     ///
     /// ```no_run
-    /// use ev3dev_lang_rust::motors::LargeMotor;
+    /// use ev3dev_lang_rust::motors::{LargeMotor, MotorState};
     /// use std::time::Duration;
     ///
     /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
@@ -826,10 +1210,9 @@ impl TachoMotor {
     /// motor.run_timed(Some(Duration::from_secs(5)))?;
     ///
     /// let cond = || {
-    ///     motor.get_state()
-    ///         .unwrap_or_else(|_| vec![])
-    ///         .iter()
-    ///         .all(|s| s != LargeMotor::STATE_RUNNING)
+    ///     !motor.get_state()
+    ///         .map(|state| state.contains(MotorState::RUNNING))
+    ///         .unwrap_or(false)
     /// };
     /// motor.wait(cond, None);
     ///
@@ -848,7 +1231,7 @@ impl TachoMotor {
         }
     }
 
-    /// Wait while the `state` is in the vector `self.get_state()` or the `timeout` is reached.
+    /// Wait while the flag `state` is set in `self.get_state()` or the `timeout` is reached.
     ///
     /// If the `timeout` is `None` it will wait an infinite time.
     ///
@@ -878,7 +1261,7 @@ impl TachoMotor {
         }
     }
 
-    /// Wait until the `state` is in the vector `self.get_state()` or the `timeout` is reached.
+    /// Wait until the flag `state` is set in `self.get_state()` or the `timeout` is reached.
     ///
     /// If the `timeout` is `None` it will wait an infinite time.
     ///
@@ -955,3 +1338,25 @@ impl From<MediumMotor> for TachoMotor {
         }
     }
 }
+
+impl Device for TachoMotor {
+    fn get_attribute(&self, name: &str) -> Attribute {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.get_attribute(name),
+            TachoMotorInner::MediumMotor { ref motor } => motor.get_attribute(name),
+        }
+    }
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.try_get_attribute(name),
+            TachoMotorInner::MediumMotor { ref motor } => motor.try_get_attribute(name),
+        }
+    }
+
+    fn reconnect(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref mut motor } => motor.reconnect(port),
+            TachoMotorInner::MediumMotor { ref mut motor } => motor.reconnect(port),
+        }
+    }
+}
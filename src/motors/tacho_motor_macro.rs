@@ -35,6 +35,9 @@ macro_rules! tacho_motor {
         /// This will also have the effect of stopping the motor.
         pub const COMMAND_RESET: &'static str = "reset";
 
+        /// Maximal value of `ramp_up_sp` and `ramp_down_sp` in milliseconds accepted by the kernel.
+        pub const MAX_RAMP_SP: i32 = 60_000;
+
         /// A positive duty cycle will cause the motor to rotate clockwise.
         pub const POLARITY_NORMAL: &'static str = "normal";
 
@@ -177,6 +180,8 @@ macro_rules! tacho_motor {
         ///
         /// Units are in percent.
         /// Valid values are -100 to 100. A negative value causes the motor to rotate in reverse.
+        /// Values outside of this range are clamped.
+        /// While the motor runs with `run-direct`, the new duty cycle takes effect immediately.
         ///
         /// # Examples
         ///
@@ -202,17 +207,27 @@ macro_rules! tacho_motor {
         /// # Ok(())
         /// # }
         pub fn set_duty_cycle_sp(&self, duty_cycle: i32) -> Ev3Result<()> {
-            self.get_attribute("duty_cycle_sp").set(duty_cycle)
+            self.get_attribute("duty_cycle_sp")
+                .set(duty_cycle.clamp(-100, 100))
         }
 
         /// Returns the current polarity of the motor.
-        pub fn get_polarity(&self) -> Ev3Result<String> {
+        pub fn get_polarity(&self) -> Ev3Result<$crate::motors::Polarity> {
             self.get_attribute("polarity").get()
         }
 
         /// Sets the polarity of the motor.
-        pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
-            self.get_attribute("polarity").set_str_slice(polarity)
+        pub fn set_polarity(&self, polarity: $crate::motors::Polarity) -> Ev3Result<()> {
+            self.get_attribute("polarity")
+                .set_str_slice(polarity.as_str())
+        }
+
+        /// Inverts the current polarity of the motor, e.g. for a wheel that is mounted backwards.
+        /// Returns the new polarity.
+        pub fn toggle_polarity(&self) -> Ev3Result<$crate::motors::Polarity> {
+            let polarity = self.get_polarity()?.inverted();
+            self.set_polarity(polarity)?;
+            Ok(polarity)
         }
 
         /// Returns the current position of the motor in pulses of the rotary encoder.
@@ -275,32 +290,90 @@ macro_rules! tacho_motor {
 
         /// Returns the proportional pub constant for the position PID.
         pub fn get_hold_pid_kp(&self) -> Ev3Result<f32> {
-            self.get_attribute("hold_pid_kp").get()
+            self.get_attribute("hold_pid/Kp").get()
         }
 
         /// Sets the proportional pub constant for the position PID.
         pub fn set_hold_pid_kp(&self, kp: f32) -> Ev3Result<()> {
-            self.get_attribute("hold_pid_kp").set(kp)
+            self.get_attribute("hold_pid/Kp").set(kp)
         }
 
         /// Returns the integral pub constant for the position PID.
         pub fn get_hold_pid_ki(&self) -> Ev3Result<f32> {
-            self.get_attribute("hold_pid_ki").get()
+            self.get_attribute("hold_pid/Ki").get()
         }
 
         /// Sets the integral pub constant for the position PID.
         pub fn set_hold_pid_ki(&self, ki: f32) -> Ev3Result<()> {
-            self.get_attribute("hold_pid_ki").set(ki)
+            self.get_attribute("hold_pid/Ki").set(ki)
         }
 
         /// Returns the derivative pub constant for the position PID.
         pub fn get_hold_pid_kd(&self) -> Ev3Result<f32> {
-            self.get_attribute("hold_pid_kd").get()
+            self.get_attribute("hold_pid/Kd").get()
         }
 
         /// Sets the derivative pub constant for the position PID.
         pub fn set_hold_pid_kd(&self, kd: f32) -> Ev3Result<()> {
-            self.get_attribute("hold_pid_kd").set(kd)
+            self.get_attribute("hold_pid/Kd").set(kd)
+        }
+
+        /// Returns the constants `{controller}/Kp`, `{controller}/Ki` and `{controller}/Kd`.
+        fn get_pid(&self, controller: &str) -> Ev3Result<$crate::motors::Pid> {
+            let constant = |name: &str| {
+                self.driver
+                    .try_get_attribute(&format!("{}/{}", controller, name))?
+                    .get::<f32>()
+            };
+
+            Ok($crate::motors::Pid {
+                kp: constant("Kp")?,
+                ki: constant("Ki")?,
+                kd: constant("Kd")?,
+            })
+        }
+
+        /// Sets the constants `{controller}/Kp`, `{controller}/Ki` and `{controller}/Kd`.
+        fn set_pid(&self, controller: &str, pid: $crate::motors::Pid) -> Ev3Result<()> {
+            let constant = |name: &str, value: f32| {
+                self.driver
+                    .try_get_attribute(&format!("{}/{}", controller, name))?
+                    .set(value)
+            };
+
+            constant("Kp", pid.kp)?;
+            constant("Ki", pid.ki)?;
+            constant("Kd", pid.kd)
+        }
+
+        /// Returns all constants of the position PID.
+        /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// // Init a tacho motor.
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// // Stiffen the position hold and restore the original tuning afterwards.
+        /// let pid = motor.get_hold_pid()?;
+        /// motor.set_hold_pid(ev3dev_lang_rust::motors::Pid { kp: pid.kp * 2.0, ..pid })?;
+        /// // ...
+        /// motor.set_hold_pid(pid)?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn get_hold_pid(&self) -> Ev3Result<$crate::motors::Pid> {
+            self.get_pid("hold_pid")
+        }
+
+        /// Sets all constants of the position PID.
+        /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+        pub fn set_hold_pid(&self, pid: $crate::motors::Pid) -> Ev3Result<()> {
+            self.set_pid("hold_pid", pid)
         }
 
         /// Returns the maximum value that is accepted by the `speed_sp` attribute.
@@ -312,6 +385,136 @@ macro_rules! tacho_motor {
             self.get_attribute("max_speed").get()
         }
 
+        /// Returns the `max_speed` attribute. The value is read once and cached afterwards.
+        fn get_max_speed_cached(&self) -> Ev3Result<i32> {
+            if let Some(max_speed) = self.max_speed.get() {
+                return Ok(max_speed);
+            }
+
+            let max_speed = self.get_max_speed()?;
+            self.max_speed.set(Some(max_speed));
+            Ok(max_speed)
+        }
+
+        /// Returns the `count_per_rot` attribute. The value is read once and cached afterwards.
+        fn get_count_per_rot_cached(&self) -> Ev3Result<i32> {
+            if let Some(count_per_rot) = self.count_per_rot.get() {
+                return Ok(count_per_rot);
+            }
+
+            let count_per_rot = self.get_count_per_rot()?;
+            self.count_per_rot.set(Some(count_per_rot));
+            Ok(count_per_rot)
+        }
+
+        /// Reads the constant `max_speed`, `count_per_rot` and `count_per_m` attributes into the cache
+        /// and drops the cached `commands` and `stop_actions`, e.g. after a `reconnect`.
+        /// Attributes that can not be read, e.g. `count_per_m` of rotational motors, stay `None`.
+        /// The helpers read missing `max_speed` and `count_per_rot` values again on first use.
+        fn read_constants(&self) {
+            *self.commands.borrow_mut() = None;
+            *self.stop_actions.borrow_mut() = None;
+            let read = |name: &str| {
+                self.driver
+                    .try_get_attribute(name)
+                    .and_then(|attribute| attribute.get::<i32>())
+                    .ok()
+            };
+            self.max_speed.set(read("max_speed"));
+            self.count_per_rot.set(read("count_per_rot"));
+            self.count_per_m.set(read("count_per_m"));
+        }
+
+        /// Returns the cached `max_speed` attribute, `None` if it could not be read yet.
+        ///
+        /// The constant attributes are read when the motor is constructed (e.g. by `find`) or reconnected
+        /// and are never changed by the driver, not even by the `reset` command.
+        pub fn max_speed(&self) -> Option<i32> {
+            self.max_speed.get()
+        }
+
+        /// Returns the cached `count_per_rot` attribute, `None` if it could not be read yet (see `max_speed()`).
+        pub fn count_per_rot(&self) -> Option<i32> {
+            self.count_per_rot.get()
+        }
+
+        /// Returns the cached `count_per_m` attribute, `None` if it could not be read yet (see `max_speed()`).
+        /// (linear motors only)
+        pub fn count_per_m(&self) -> Option<i32> {
+            self.count_per_m.get()
+        }
+
+        /// Returns the current position of the motor in degrees.
+        /// The cached `count_per_rot` attribute is used, see `count_per_rot()`.
+        pub fn get_position_degrees(&self) -> Ev3Result<f32> {
+            let count_per_rot = self.get_count_per_rot_cached()?;
+            Ok(self.get_position()? as f32 * 360.0 / count_per_rot as f32)
+        }
+
+        /// Runs the motor by the given amount of `degrees` relative to the current position,
+        /// see `run_to_rel_pos`. A negative value rotates the motor backwards.
+        /// The cached `count_per_rot` attribute is used, see `count_per_rot()`.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// // Quarter turn.
+        /// motor.set_speed_sp(500)?;
+        /// motor.run_to_rel_degrees(90.0)?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn run_to_rel_degrees(&self, degrees: f32) -> Ev3Result<()> {
+            self.run_to_rel_rotations(degrees / 360.0)
+        }
+
+        /// Runs the motor by the given amount of `rotations` relative to the current position,
+        /// see `run_to_rel_pos`. A negative value rotates the motor backwards.
+        /// The cached `count_per_rot` attribute is used, see `count_per_rot()`.
+        pub fn run_to_rel_rotations(&self, rotations: f32) -> Ev3Result<()> {
+            self.run_to_rel($crate::motors::Rotations(rotations))
+        }
+
+        /// Sets the target speed as percentage of `max_speed` used for all run-* commands except run-direct.
+        ///
+        /// The `percent` is clamped to the range [-100, 100], so the `speed_sp` never exceeds `max_speed`.
+        /// A negative value causes the motor to rotate in reverse
+        /// with the exception of run-to-*-pos commands where the sign is ignored.
+        /// The cached `max_speed` attribute is used, see `max_speed()`.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// // Init a tacho motor.
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// // Run the motor at half of its maximum speed.
+        /// motor.set_speed_sp_percent(50.0)?;
+        /// motor.run_forever()?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn set_speed_sp_percent(&self, percent: f32) -> Ev3Result<()> {
+            let max_speed = self.get_max_speed_cached()?;
+            let percent = percent.clamp(-100.0, 100.0);
+            self.set_speed_sp((max_speed as f32 * percent / 100.0).round() as i32)
+        }
+
+        /// Returns the current motor speed as percentage of `max_speed`.
+        /// The cached `max_speed` attribute is used, see `max_speed()`.
+        pub fn get_speed_percent(&self) -> Ev3Result<f32> {
+            let max_speed = self.get_max_speed_cached()?;
+            Ok(self.get_speed()? as f32 * 100.0 / max_speed as f32)
+        }
+
         /// Returns the current target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
         ///
         /// Units are in tacho counts.
@@ -324,8 +527,8 @@ macro_rules! tacho_motor {
 
         /// Sets the target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
         ///
-        /// Units are in tacho counts.
-        /// You can use the value returned by `counts_per_rot` to convert tacho counts to/from rotations or degrees.
+        /// Plain numbers are in tacho counts. `Degrees` and `Rotations` are converted
+        /// with the cached `count_per_rot` attribute, see `IntoCounts`.
         ///
         /// The range is -2,147,483,648 and +2,147,483,647 tachometer counts (32-bit signed integer).
         ///
@@ -356,8 +559,12 @@ macro_rules! tacho_motor {
         /// # Ok(())
         /// # }
         /// ```
-        pub fn set_position_sp(&self, position_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("position_sp").set(position_sp)
+        pub fn set_position_sp<P>(&self, position_sp: P) -> Ev3Result<()>
+        where
+            P: $crate::motors::IntoCounts,
+        {
+            let position_sp = position_sp.into_counts(|| self.get_count_per_rot_cached())?;
+            self.get_attribute("position_sp").set(position_sp.0)
         }
 
         /// Returns the current motor speed in tacho counts per second.
@@ -368,6 +575,33 @@ macro_rules! tacho_motor {
             self.get_attribute("speed").get()
         }
 
+        /// Returns the current position and speed in tacho counts, read back-to-back.
+        ///
+        /// Both attributes are read uncached and directly after each other, see `Attribute::get_uncached`,
+        /// to minimize the time between the two samples, e.g. for odometry.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// let (position, speed) = motor.get_position_and_speed()?;
+        /// println!("Position {} at speed {}", position, speed);
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn get_position_and_speed(&self) -> Ev3Result<(i32, i32)> {
+            let position = self.get_attribute("position");
+            let speed = self.get_attribute("speed");
+
+            let position = position.get_uncached()?;
+            let speed = speed.get_uncached()?;
+            Ok((position, speed))
+        }
+
         /// Returns the target speed in tacho counts per second used for all run-* commands except run-direct.
         ///
         /// A negative value causes the motor to rotate in reverse
@@ -384,8 +618,11 @@ macro_rules! tacho_motor {
         /// with the exception of run-to-*-pos commands where the sign is ignored.
         /// Use the `count_per_rot` attribute to convert RPM or deg/sec to tacho counts per second.
         /// Use the `count_per_m` attribute to convert m/s to tacho counts per second.
-        pub fn set_speed_sp(&self, speed_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("speed_sp").set(speed_sp)
+        pub fn set_speed_sp<S>(&self, speed_sp: S) -> Ev3Result<()>
+        where
+            S: Into<$crate::motors::CountsPerSec>,
+        {
+            self.get_attribute("speed_sp").set(speed_sp.into().0)
         }
 
         /// Returns the current ramp up setpoint.
@@ -408,6 +645,18 @@ macro_rules! tacho_motor {
             self.get_attribute("ramp_up_sp").set(ramp_up_sp)
         }
 
+        /// Returns the current ramp up setpoint as `Duration`, see `get_ramp_up_sp`.
+        pub fn get_ramp_up(&self) -> Ev3Result<Duration> {
+            Ok(Duration::from_millis(self.get_ramp_up_sp()?.max(0) as u64))
+        }
+
+        /// Sets the ramp up setpoint from a `Duration`, see `set_ramp_up_sp`.
+        ///
+        /// The kernel rejects ramp setpoints above 60 seconds, so longer durations are clamped to 60 seconds.
+        pub fn set_ramp_up(&self, ramp_up: Duration) -> Ev3Result<()> {
+            self.set_ramp_up_sp(ramp_up.as_millis().min(Self::MAX_RAMP_SP as u128) as i32)
+        }
+
         /// Returns the current ramp down setpoint.
         ///
         /// Units are in milliseconds and must be positive. When set to a non-zero value,
@@ -428,58 +677,142 @@ macro_rules! tacho_motor {
             self.get_attribute("ramp_down_sp").set(ramp_down_sp)
         }
 
+        /// Returns the current ramp down setpoint as `Duration`, see `get_ramp_down_sp`.
+        pub fn get_ramp_down(&self) -> Ev3Result<Duration> {
+            Ok(Duration::from_millis(self.get_ramp_down_sp()?.max(0) as u64))
+        }
+
+        /// Sets the ramp down setpoint from a `Duration`, see `set_ramp_down_sp`.
+        ///
+        /// The kernel rejects ramp setpoints above 60 seconds, so longer durations are clamped to 60 seconds.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        /// use std::time::Duration;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// motor.set_ramp_up(Duration::from_millis(500))?;
+        /// motor.set_ramp_down(Duration::from_millis(250))?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn set_ramp_down(&self, ramp_down: Duration) -> Ev3Result<()> {
+            self.set_ramp_down_sp(ramp_down.as_millis().min(Self::MAX_RAMP_SP as u128) as i32)
+        }
+
+        /// Returns all constants of the speed regulation PID.
+        /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+        pub fn get_speed_pid(&self) -> Ev3Result<$crate::motors::Pid> {
+            self.get_pid("speed_pid")
+        }
+
+        /// Sets all constants of the speed regulation PID.
+        /// Returns `Ev3Error::InternalError` naming the attribute if the motor driver does not provide one of the constants.
+        pub fn set_speed_pid(&self, pid: $crate::motors::Pid) -> Ev3Result<()> {
+            self.set_pid("speed_pid", pid)
+        }
+
         /// Returns the proportional pub constant for the speed regulation PID.
         pub fn get_speed_pid_kp(&self) -> Ev3Result<f32> {
-            self.get_attribute("speed_pid_kp").get()
+            self.get_attribute("speed_pid/Kp").get()
         }
 
         /// Sets the proportional pub constant for the speed regulation PID.
         pub fn set_speed_pid_kp(&self, kp: f32) -> Ev3Result<()> {
-            self.get_attribute("speed_pid_kp").set(kp)
+            self.get_attribute("speed_pid/Kp").set(kp)
         }
 
         /// Returns the integral pub constant for the speed regulation PID.
         pub fn get_speed_pid_ki(&self) -> Ev3Result<f32> {
-            self.get_attribute("speed_pid_ki").get()
+            self.get_attribute("speed_pid/Ki").get()
         }
 
         /// Sets the integral pub constant for the speed regulation PID.
         pub fn set_speed_pid_ki(&self, ki: f32) -> Ev3Result<()> {
-            self.get_attribute("speed_pid_ki").set(ki)
+            self.get_attribute("speed_pid/Ki").set(ki)
         }
 
         /// Returns the derivative pub constant for the speed regulation PID.
         pub fn get_speed_pid_kd(&self) -> Ev3Result<f32> {
-            self.get_attribute("speed_pid_kd").get()
+            self.get_attribute("speed_pid/Kd").get()
         }
 
         /// Sets the derivative pub constant for the speed regulation PID.
         pub fn set_speed_pid_kd(&self, kd: f32) -> Ev3Result<()> {
-            self.get_attribute("speed_pid_kd").set(kd)
+            self.get_attribute("speed_pid/Kd").set(kd)
         }
 
-        /// Returns a list of state flags.
-        pub fn get_state(&self) -> Ev3Result<Vec<String>> {
-            self.get_attribute("state").get_vec()
+        /// Returns the state flags, see `MotorState`.
+        ///
+        /// Use `is_running()`, `is_ramping()`, `is_holding()`, `is_overloaded()` and `is_stalled()`
+        /// to check for a single flag without blocking.
+        pub fn get_state(&self) -> Ev3Result<$crate::motors::MotorState> {
+            self.get_attribute("state").get()
         }
 
         /// Returns the current stop action.
         ///
         /// The value determines the motors behavior when command is set to stop.
-        pub fn get_stop_action(&self) -> Ev3Result<String> {
+        pub fn get_stop_action(&self) -> Ev3Result<$crate::motors::StopAction> {
             self.get_attribute("stop_action").get()
         }
 
         /// Sets the stop action.
         ///
         /// The value determines the motors behavior when command is set to stop.
-        pub fn set_stop_action(&self, stop_action: &str) -> Ev3Result<()> {
-            self.get_attribute("stop_action").set_str_slice(stop_action)
+        /// Returns `Ev3Error::InternalError` if the motor controller does not support the `stop_action`,
+        /// see `get_stop_actions()`.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::{LargeMotor, StopAction};
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// motor.set_stop_action(StopAction::Hold)?;
+        /// assert_eq!(motor.get_stop_action()?, StopAction::Hold);
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn set_stop_action(&self, stop_action: $crate::motors::StopAction) -> Ev3Result<()> {
+            let stop_actions = self.get_stop_actions()?;
+            if !stop_actions.iter().any(|a| a == stop_action.as_str()) {
+                return Err(Ev3Error::InternalError {
+                    msg: format!(
+                        "Stop action `{}` is not supported, use one of {:?}",
+                        stop_action, stop_actions
+                    ),
+                });
+            }
+            self.get_attribute("stop_action")
+                .set_str_slice(stop_action.as_str())
         }
 
         /// Returns a list of stop actions supported by the motor controller.
+        /// The constant `stop_actions` attribute is read once and cached afterwards.
         pub fn get_stop_actions(&self) -> Ev3Result<Vec<String>> {
-            self.get_attribute("stop_actions").get_vec()
+            if let Some(ref stop_actions) = *self.stop_actions.borrow() {
+                return Ok(stop_actions.clone());
+            }
+
+            let stop_actions = self.get_attribute("stop_actions").get_vec()?;
+            *self.stop_actions.borrow_mut() = Some(stop_actions.clone());
+            Ok(stop_actions)
+        }
+
+        /// Returns the supported stop actions of the motor controller, unknown stop actions are skipped.
+        pub fn get_available_stop_actions(&self) -> Ev3Result<Vec<$crate::motors::StopAction>> {
+            Ok(self
+                .get_stop_actions()?
+                .iter()
+                .filter_map(|stop_action| stop_action.parse().ok())
+                .collect())
         }
 
         /// Returns the current amount of time the motor will run when using the run-timed command.
@@ -496,16 +829,61 @@ macro_rules! tacho_motor {
             self.get_attribute("time_sp").set(time_sp)
         }
 
+        /// Sets the run time of the run-timed command from a `Duration`, see `set_time_sp`.
+        ///
+        /// Durations longer than `i32::MAX` milliseconds are clamped.
+        pub fn set_time(&self, time: Duration) -> Ev3Result<()> {
+            self.set_time_sp(time.as_millis().min(i32::MAX as u128) as i32)
+        }
+
+        /// Returns the commands supported by the motor, e.g. `run-forever` or `stop`.
+        /// The constant `commands` attribute is read once and cached afterwards.
+        pub fn get_available_commands(&self) -> Ev3Result<Vec<String>> {
+            if let Some(ref commands) = *self.commands.borrow() {
+                return Ok(commands.clone());
+            }
+
+            let commands = self.driver.try_get_attribute("commands")?.get_vec()?;
+            *self.commands.borrow_mut() = Some(commands.clone());
+            Ok(commands)
+        }
+
+        /// Sends the `command` to the motor. All run commands are sent this way.
+        ///
+        /// Returns `Ev3Error::InternalError` if the command is not listed in `get_available_commands()`,
+        /// instead of the `EINVAL` of the kernel. Errors of reading the `commands` attribute are returned
+        /// and no command is sent. Use `send_command_unchecked()` to skip this validation.
+        pub fn send_command(&self, command: &str) -> Ev3Result<()> {
+            let commands = self.get_available_commands()?;
+            if !commands.iter().any(|c| c == command) {
+                return Err(Ev3Error::InternalError {
+                    msg: format!(
+                        "Motor command `{}` is not supported, use one of {:?}",
+                        command, commands
+                    ),
+                });
+            }
+            self.set_command(command)
+        }
+
+        /// Sends the `command` to the motor without checking it against the list of supported commands.
+        /// This can be used to force undocumented commands or motors without a readable `commands` attribute.
+        /// `stop` and `reset` are sent this way, so stopping a motor never depends on reading `commands`.
+        pub fn send_command_unchecked(&self, command: &str) -> Ev3Result<()> {
+            self.set_command(command)
+        }
+
         /// Runs the motor using the duty cycle specified by `duty_cycle_sp`.
         ///
         /// Unlike other run commands, changing `duty_cycle_sp` while running will take effect immediately.
+        /// The duty cycle is applied open loop: `run-direct` bypasses `ramp_up_sp`, `ramp_down_sp` and the speed PID.
         pub fn run_direct(&self) -> Ev3Result<()> {
-            self.set_command(Self::COMMAND_RUN_DIRECT)
+            self.send_command(Self::COMMAND_RUN_DIRECT)
         }
 
         /// Causes the motor to run until another command is sent.
         pub fn run_forever(&self) -> Ev3Result<()> {
-            self.set_command(Self::COMMAND_RUN_FOREVER)
+            self.send_command(Self::COMMAND_RUN_FOREVER)
         }
 
         /// Runs the motor to an absolute position specified by `position_sp`
@@ -515,7 +893,53 @@ macro_rules! tacho_motor {
             if let Some(p) = position_sp {
                 self.set_position_sp(p)?;
             }
-            self.set_command(Self::COMMAND_RUN_TO_ABS_POS)
+            self.send_command(Self::COMMAND_RUN_TO_ABS_POS)
+        }
+
+        /// Runs the motor to the absolute `position`, see `run_to_abs_pos`.
+        /// The `position` can be given in any unit of `IntoCounts`, e.g. `Rotations`.
+        pub fn run_to_abs<P>(&self, position: P) -> Ev3Result<()>
+        where
+            P: $crate::motors::IntoCounts,
+        {
+            self.set_position_sp(position)?;
+            self.send_command(Self::COMMAND_RUN_TO_ABS_POS)
+        }
+
+        /// Runs the motor to the absolute position `position_sp` and waits until the motor has stopped.
+        ///
+        /// Returns the final position of the motor after the motor stopped using the command specified in `stop_action`.
+        /// Returns `Ev3Error::Timeout` if the motor is still running after the `timeout`, e.g. because it is stalled.
+        /// If the `timeout` is `None` it will wait an infinite time.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        /// use std::time::Duration;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// // Init a tacho motor.
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// let position = motor.run_to_abs_pos_blocking(360, Some(Duration::from_secs(5)))?;
+        ///
+        /// println!("Motor stopped at position {}", position);
+        /// # Ok(())
+        /// # }
+        /// ```
+        #[cfg(target_os = "linux")]
+        pub fn run_to_abs_pos_blocking(
+            &self,
+            position_sp: i32,
+            timeout: Option<Duration>,
+        ) -> Ev3Result<i32> {
+            self.run_to_abs_pos(Some(position_sp))?;
+
+            if !self.wait_until_not_moving(timeout) {
+                return Err(Ev3Error::Timeout);
+            }
+            self.get_position()
         }
 
         /// Runs the motor to a position relative to the current position value.
@@ -526,7 +950,31 @@ macro_rules! tacho_motor {
             if let Some(p) = position_sp {
                 self.set_position_sp(p)?;
             }
-            self.set_command(Self::COMMAND_RUN_TO_REL_POS)
+            self.send_command(Self::COMMAND_RUN_TO_REL_POS)
+        }
+
+        /// Runs the motor to the `position` relative to the current position, see `run_to_rel_pos`.
+        /// The `position` can be given in any unit of `IntoCounts`, e.g. `Degrees`.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::{Degrees, LargeMotor};
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// motor.set_speed_sp(500)?;
+        /// motor.run_to_rel(Degrees(90.0))?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn run_to_rel<P>(&self, position: P) -> Ev3Result<()>
+        where
+            P: $crate::motors::IntoCounts,
+        {
+            self.set_position_sp(position)?;
+            self.send_command(Self::COMMAND_RUN_TO_REL_POS)
         }
 
         /// Run the motor for the amount of time specified in `time_sp`
@@ -534,61 +982,200 @@ macro_rules! tacho_motor {
         /// and then stops the motor using the command specified by `stop_action`.
         pub fn run_timed(&self, time_sp: Option<Duration>) -> Ev3Result<()> {
             if let Some(duration) = time_sp {
-                let p = duration.as_millis() as i32;
-                self.set_time_sp(p)?;
+                self.set_time(duration)?;
             }
-            self.set_command(Self::COMMAND_RUN_TIMED)
+            self.send_command(Self::COMMAND_RUN_TIMED)
+        }
+
+        /// Runs the motor for the given `time_sp` and waits until the motor has stopped
+        /// using the command specified by `stop_action`.
+        ///
+        /// The state is awaited with the `wait` module, see `wait_until_not_moving`.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        /// use std::time::Duration;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// motor.set_speed_sp(500)?;
+        /// motor.run_timed_blocking(Duration::from_secs(2))?;
+        ///
+        /// println!("Motor has stopped!");
+        /// # Ok(())
+        /// # }
+        /// ```
+        #[cfg(target_os = "linux")]
+        pub fn run_timed_blocking(&self, time_sp: Duration) -> Ev3Result<()> {
+            self.run_timed(Some(time_sp))?;
+            self.wait_until_not_moving(None);
+            Ok(())
         }
 
         /// Stop any of the run commands before they are complete using the command specified by `stop_action`.
         pub fn stop(&self) -> Ev3Result<()> {
-            self.set_command(Self::COMMAND_STOP)
+            self.send_command_unchecked(Self::COMMAND_STOP)
         }
 
         /// Resets all of the motor parameter attributes to their default values.
         /// This will also have the effect of stopping the motor.
         pub fn reset(&self) -> Ev3Result<()> {
-            self.set_command(Self::COMMAND_RESET)
+            self.send_command_unchecked(Self::COMMAND_RESET)
+        }
+
+        /// Returns a builder to configure several setpoints at once, see `MotorConfig`.
+        pub fn configure(&self) -> $crate::motors::MotorConfig {
+            $crate::motors::MotorConfig::new(self.clone().into())
+        }
+
+        /// Reads the state and setpoints of the motor once, see `MotorSnapshot`.
+        pub fn snapshot(&self) -> Ev3Result<$crate::motors::MotorSnapshot> {
+            Ok($crate::motors::MotorSnapshot {
+                driver_name: self.get_driver_name()?,
+                address: self.get_address()?,
+                state: self
+                    .get_state()?
+                    .names()
+                    .into_iter()
+                    .map(str::to_owned)
+                    .collect(),
+                position: self.get_position()?,
+                speed: self.get_speed()?,
+                duty_cycle: self.get_duty_cycle()?,
+                polarity: self.get_polarity()?,
+                stop_action: self.get_stop_action()?,
+                speed_sp: self.get_speed_sp()?,
+                duty_cycle_sp: self.get_duty_cycle_sp()?,
+                position_sp: self.get_position_sp()?,
+                time_sp: self.get_time_sp()?,
+                ramp_up_sp: self.get_ramp_up_sp()?,
+                ramp_down_sp: self.get_ramp_down_sp()?,
+            })
+        }
+
+        /// Resets the motor with the `reset` command and reads back the constant motor attributes.
+        ///
+        /// The kernel `reset` command already stops the motor, sets `position` to 0 and restores
+        /// `speed_sp`, `duty_cycle_sp`, `position_sp`, `time_sp`, `ramp_up_sp`, `ramp_down_sp`, `stop_action`,
+        /// `polarity` and the hold and speed PID constants to their driver defaults.
+        /// Additionally this refreshes the cached `max_speed`, `count_per_rot` and `count_per_m`
+        /// and checks that `count_per_rot` and `max_speed` are readable.
+        ///
+        /// Values cached by `Attribute::set_if_changed` are not aware of the reset.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let motor = LargeMotor::find()?;
+        /// motor.reset_to_defaults()?;
+        ///
+        /// motor.set_speed_sp_percent(50.0)?;
+        /// motor.run_forever()?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn reset_to_defaults(&self) -> Ev3Result<()> {
+            self.reset()?;
+
+            self.read_constants();
+            self.get_count_per_rot_cached()?;
+            self.get_max_speed_cached()?;
+            Ok(())
         }
 
         /// Power is being sent to the motor.
         pub fn is_running(&self) -> Ev3Result<bool> {
             Ok(self
                 .get_state()?
-                .iter()
-                .any(|state| state == Self::STATE_RUNNING))
+                .contains($crate::motors::MotorState::RUNNING))
         }
 
         /// The motor is ramping up or down and has not yet reached a pub constant output level.
         pub fn is_ramping(&self) -> Ev3Result<bool> {
             Ok(self
                 .get_state()?
-                .iter()
-                .any(|state| state == Self::STATE_RAMPING))
+                .contains($crate::motors::MotorState::RAMPING))
         }
 
         /// The motor is not turning, but rather attempting to hold a fixed position.
         pub fn is_holding(&self) -> Ev3Result<bool> {
             Ok(self
                 .get_state()?
-                .iter()
-                .any(|state| state == Self::STATE_HOLDING))
+                .contains($crate::motors::MotorState::HOLDING))
         }
 
         /// The motor is turning as fast as possible, but cannot reach its `speed_sp`.
         pub fn is_overloaded(&self) -> Ev3Result<bool> {
             Ok(self
                 .get_state()?
-                .iter()
-                .any(|state| state == Self::STATE_OVERLOADED))
+                .contains($crate::motors::MotorState::OVERLOADED))
         }
 
         /// The motor is trying to run but is not turning at all.
         pub fn is_stalled(&self) -> Ev3Result<bool> {
             Ok(self
                 .get_state()?
-                .iter()
-                .any(|state| state == Self::STATE_STALLED))
+                .contains($crate::motors::MotorState::STALLED))
+        }
+
+        /// Detects a stall from the actual `speed` compared to the `speed_sp`,
+        /// independent of the `stalled` state flag of the motor driver.
+        ///
+        /// The speed is sampled every 10 milliseconds during the `window`.
+        /// Returns `true` if the motor is running and the absolute `speed` stays below
+        /// `min_speed_ratio` times the absolute `speed_sp` for every sample of the `window`.
+        /// Returns `false` as soon as one sample is fast enough or the motor is not running,
+        /// so a moving motor is detected after a single sample.
+        ///
+        /// A zero `window` takes a single sample without sleeping, which is cheap enough
+        /// to be used as condition of `wait`. The `speed_sp` is not used by the `run-direct` command.
+        /// Note that the motor also runs slower than `speed_sp` while it accelerates after a start.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::LargeMotor;
+        /// use std::time::Duration;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// // Init a tacho motor.
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// // Move an arm until it hits its mechanical limit.
+        /// motor.set_speed_sp(200)?;
+        /// motor.run_forever()?;
+        /// while !motor.detect_stall(0.2, Duration::from_millis(200))? {}
+        /// motor.stop()?;
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn detect_stall(&self, min_speed_ratio: f32, window: Duration) -> Ev3Result<bool> {
+            const SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+            let start = std::time::Instant::now();
+            loop {
+                if !self.is_running()? {
+                    return Ok(false);
+                }
+
+                let speed_sp = self.get_speed_sp()?.abs() as f32;
+                let speed = self.get_speed()?.abs() as f32;
+                if speed_sp == 0.0 || speed >= speed_sp * min_speed_ratio {
+                    return Ok(false);
+                }
+
+                let elapsed = start.elapsed();
+                if elapsed >= window {
+                    return Ok(true);
+                }
+                std::thread::sleep(SAMPLE_INTERVAL.min(window - elapsed));
+            }
         }
 
         /// Wait until condition `cond` returns true or the `timeout` is reached.
@@ -599,7 +1186,7 @@ macro_rules! tacho_motor {
         /// # Examples
         ///
         /// ```no_run
-        /// use ev3dev_lang_rust::motors::LargeMotor;
+        /// use ev3dev_lang_rust::motors::{LargeMotor, MotorState};
         /// use std::time::Duration;
         ///
         /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
@@ -609,10 +1196,9 @@ macro_rules! tacho_motor {
         /// motor.run_timed(Some(Duration::from_secs(5)))?;
         ///
         /// let cond = || {
-        ///     motor.get_state()
-        ///         .unwrap_or_else(|_| vec![])
-        ///         .iter()
-        ///         .all(|s| s != LargeMotor::STATE_RUNNING)
+        ///     !motor.get_state()
+        ///         .map(|state| state.contains(MotorState::RUNNING))
+        ///         .unwrap_or(false)
         /// };
         /// motor.wait(cond, None);
         ///
@@ -629,7 +1215,7 @@ macro_rules! tacho_motor {
             wait::wait(fd, cond, timeout)
         }
 
-        /// Wait while the `state` is in the vector `self.get_state()` or the `timeout` is reached.
+        /// Wait while the flag `state` is set in `self.get_state()` or the `timeout` is reached.
         ///
         /// If the `timeout` is `None` it will wait an infinite time.
         ///
@@ -654,15 +1240,15 @@ macro_rules! tacho_motor {
         #[cfg(target_os = "linux")]
         pub fn wait_while(&self, state: &str, timeout: Option<Duration>) -> bool {
             let cond = || {
-                self.get_state()
-                    .unwrap_or_else(|_| vec![])
-                    .iter()
-                    .all(|s| s != state)
+                !self
+                    .get_state()
+                    .map(|s| s.contains_name(state))
+                    .unwrap_or(false)
             };
             self.wait(cond, timeout)
         }
 
-        /// Wait until the `state` is in the vector `self.get_state()` or the `timeout` is reached.
+        /// Wait until the flag `state` is set in `self.get_state()` or the `timeout` is reached.
         ///
         /// If the `timeout` is `None` it will wait an infinite time.
         ///
@@ -688,9 +1274,8 @@ macro_rules! tacho_motor {
         pub fn wait_until(&self, state: &str, timeout: Option<Duration>) -> bool {
             let cond = || {
                 self.get_state()
-                    .unwrap_or_else(|_| vec![])
-                    .iter()
-                    .any(|s| s == state)
+                    .map(|s| s.contains_name(state))
+                    .unwrap_or(false)
             };
             self.wait(cond, timeout)
         }
@@ -0,0 +1,173 @@
+//! Unit types for positions and speeds of tacho motors.
+
+use crate::{Ev3Error, Ev3Result};
+
+/// A position or distance in tacho counts, the native unit of the `position` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Counts(pub i32);
+
+/// A position or distance in degrees of the motor shaft.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Degrees(pub f32);
+
+/// A position or distance in rotations of the motor shaft.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Rotations(pub f32);
+
+/// A speed in tacho counts per second, the native unit of the `speed` attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct CountsPerSec(pub i32);
+
+/// Checks that `count_per_rot` can be used as divisor.
+fn check_count_per_rot(count_per_rot: i32) -> Ev3Result<i32> {
+    if count_per_rot > 0 {
+        Ok(count_per_rot)
+    } else {
+        Err(Ev3Error::InternalError {
+            msg: format!("Invalid count_per_rot `{}`", count_per_rot),
+        })
+    }
+}
+
+impl Counts {
+    /// Converts the counts to degrees with the `count_per_rot` attribute of the motor.
+    pub fn to_degrees(self, count_per_rot: i32) -> Degrees {
+        Degrees(self.0 as f32 * 360.0 / count_per_rot as f32)
+    }
+
+    /// Converts the counts to rotations with the `count_per_rot` attribute of the motor.
+    pub fn to_rotations(self, count_per_rot: i32) -> Rotations {
+        Rotations(self.0 as f32 / count_per_rot as f32)
+    }
+}
+
+impl Degrees {
+    /// Converts the degrees to the nearest tacho count with the `count_per_rot` attribute of the motor.
+    pub fn to_counts(self, count_per_rot: i32) -> Counts {
+        Rotations::from(self).to_counts(count_per_rot)
+    }
+}
+
+impl Rotations {
+    /// Converts the rotations to the nearest tacho count with the `count_per_rot` attribute of the motor.
+    pub fn to_counts(self, count_per_rot: i32) -> Counts {
+        Counts((self.0 * count_per_rot as f32).round() as i32)
+    }
+}
+
+impl CountsPerSec {
+    /// Converts the speed to rotations per minute with the `count_per_rot` attribute of the motor.
+    pub fn to_rpm(self, count_per_rot: i32) -> f32 {
+        self.0 as f32 * 60.0 / count_per_rot as f32
+    }
+
+    /// Converts rotations per minute to the nearest speed in tacho counts per second
+    /// with the `count_per_rot` attribute of the motor.
+    pub fn from_rpm(rpm: f32, count_per_rot: i32) -> CountsPerSec {
+        CountsPerSec((rpm * count_per_rot as f32 / 60.0).round() as i32)
+    }
+}
+
+impl From<i32> for Counts {
+    fn from(counts: i32) -> Self {
+        Counts(counts)
+    }
+}
+
+impl From<Counts> for i32 {
+    fn from(counts: Counts) -> Self {
+        counts.0
+    }
+}
+
+impl From<Rotations> for Degrees {
+    fn from(rotations: Rotations) -> Self {
+        Degrees(rotations.0 * 360.0)
+    }
+}
+
+impl From<Degrees> for Rotations {
+    fn from(degrees: Degrees) -> Self {
+        Rotations(degrees.0 / 360.0)
+    }
+}
+
+impl From<i32> for CountsPerSec {
+    fn from(speed: i32) -> Self {
+        CountsPerSec(speed)
+    }
+}
+
+impl From<CountsPerSec> for i32 {
+    fn from(speed: CountsPerSec) -> Self {
+        speed.0
+    }
+}
+
+/// Position types accepted by the position setpoints of the tacho motors, e.g. `set_position_sp` and `run_to_rel`.
+///
+/// Implemented for plain tacho counts (`i32` and `Counts`) and for `Degrees` and `Rotations`,
+/// which are converted with the cached `count_per_rot` attribute of the motor.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{Counts, Degrees, LargeMotor, Rotations};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::find()?;
+/// motor.set_speed_sp(500)?;
+///
+/// motor.run_to_rel(Degrees(90.0))?;
+/// motor.run_to_rel(Rotations(0.25))?;
+/// motor.run_to_rel(Counts(90))?;
+/// motor.run_to_rel(90)?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait IntoCounts {
+    /// Converts the value to tacho counts.
+    /// `count_per_rot` is only called by units that depend on the motor.
+    fn into_counts<F>(self, count_per_rot: F) -> Ev3Result<Counts>
+    where
+        F: FnOnce() -> Ev3Result<i32>;
+}
+
+impl IntoCounts for i32 {
+    fn into_counts<F>(self, _count_per_rot: F) -> Ev3Result<Counts>
+    where
+        F: FnOnce() -> Ev3Result<i32>,
+    {
+        Ok(Counts(self))
+    }
+}
+
+impl IntoCounts for Counts {
+    fn into_counts<F>(self, _count_per_rot: F) -> Ev3Result<Counts>
+    where
+        F: FnOnce() -> Ev3Result<i32>,
+    {
+        Ok(self)
+    }
+}
+
+impl IntoCounts for Degrees {
+    fn into_counts<F>(self, count_per_rot: F) -> Ev3Result<Counts>
+    where
+        F: FnOnce() -> Ev3Result<i32>,
+    {
+        Ok(self.to_counts(check_count_per_rot(count_per_rot()?)?))
+    }
+}
+
+impl IntoCounts for Rotations {
+    fn into_counts<F>(self, count_per_rot: F) -> Ev3Result<Counts>
+    where
+        F: FnOnce() -> Ev3Result<i32>,
+    {
+        Ok(self.to_counts(check_count_per_rot(count_per_rot()?)?))
+    }
+}
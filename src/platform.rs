@@ -0,0 +1,201 @@
+//! Detection of the brick the program runs on.
+
+use std::fs;
+
+use crate::utils::root_path;
+
+/// Paths of the devicetree model, used if no board info is available.
+const DEVICETREE_MODEL_PATHS: &[&str] = &[
+    "/sys/firmware/devicetree/base/model",
+    "/proc/device-tree/model",
+];
+
+/// Input device of the EV3 brick buttons.
+const EV3_BUTTON_FILE: &str = "/dev/input/by-path/platform-gpio_keys-event";
+
+/// Input device of the PiStorms `GO` button.
+const PISTORMS_BUTTON_FILE: &str = "/dev/input/by-path/platform-3f804000.i2c-event";
+
+/// Input device of the FatcatLab EVB buttons.
+const EVB_BUTTON_FILE: &str = "/dev/input/by-path/platform-evb-buttons-event";
+
+/// Side of a brick status led, see `Led`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LedSide {
+    Left,
+    Right,
+}
+
+/// Color channel of a brick status led, see `Led`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LedChannel {
+    Red,
+    Green,
+}
+
+/// Brick that runs ev3dev. Determines the led channel names of `Led` and the buttons of `Ev3Button`.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::{Led, Platform};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let platform = Platform::detect();
+/// println!("Running on {:?}", platform);
+///
+/// let led = Led::for_platform(platform)?;
+/// led.set_color(Led::COLOR_GREEN)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Platform {
+    /// LEGO MINDSTORMS EV3 with two red / green status leds and six buttons.
+    Ev3,
+    /// Dexter Industries BrickPi3 with a single amber led and no buttons.
+    BrickPi3,
+    /// Mindsensors PiStorms with two red / green / blue leds and the `GO` button.
+    PiStorms,
+    /// FatcatLab EVB BeagleBone cape with the six buttons of the EV3 and no leds.
+    FatcatLab,
+    /// Unknown brick with its model name, empty if no model is available (e.g. in a container).
+    /// Handled like the EV3.
+    Unknown(String),
+}
+
+/// Detects the platform the program runs on, see `Platform::detect`.
+pub fn detect() -> Platform {
+    Platform::detect()
+}
+
+impl Platform {
+    /// Detects the platform by the model in `/sys/class/board-info`
+    /// or, if there is no board info, by the model in `/sys/firmware/devicetree/base/model`
+    /// or `/proc/device-tree/model`.
+    ///
+    /// Returns `Platform::Unknown` if none of the models is known or no model is available.
+    pub fn detect() -> Platform {
+        let mut models = board_info_models();
+        models.extend(DEVICETREE_MODEL_PATHS.iter().filter_map(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .map(|model| model.trim_end_matches('\0').to_owned())
+        }));
+
+        models
+            .iter()
+            .map(|model| Platform::from_model(model))
+            .find(|platform| !matches!(*platform, Platform::Unknown(_)))
+            .unwrap_or_else(|| Platform::Unknown(models.into_iter().next().unwrap_or_default()))
+    }
+
+    /// Maps a board model name like `LEGO MINDSTORMS EV3` to a platform.
+    fn from_model(model: &str) -> Platform {
+        let lowercase = model.to_lowercase();
+        if lowercase.contains("ev3") {
+            Platform::Ev3
+        } else if lowercase.contains("brickpi3") {
+            Platform::BrickPi3
+        } else if lowercase.contains("pistorms") {
+            Platform::PiStorms
+        } else if lowercase.contains("fatcatlab") || lowercase.contains("evb") {
+            Platform::FatcatLab
+        } else {
+            Platform::Unknown(model.trim().to_owned())
+        }
+    }
+
+    /// Returns the side and channel of the brick status led with the sysfs `name`
+    /// or `None` if the led does not belong to the brick status leds of this platform.
+    ///
+    /// The single amber led of the BrickPi3 is exposed as the red channel of the left led.
+    pub(crate) fn led_channel(&self, name: &str) -> Option<(LedSide, LedChannel)> {
+        let channel = |name: &str| {
+            if name.contains("red:") {
+                Some(LedChannel::Red)
+            } else if name.contains("green:") {
+                Some(LedChannel::Green)
+            } else {
+                None
+            }
+        };
+
+        match *self {
+            Platform::Ev3 | Platform::Unknown(_) => {
+                if !name.contains(":brick-status") && !name.contains(":ev3dev") {
+                    None
+                } else if name.contains("led0:") || name.contains("left:") {
+                    channel(name).map(|channel| (LedSide::Left, channel))
+                } else if name.contains("led1:") || name.contains("right:") {
+                    channel(name).map(|channel| (LedSide::Right, channel))
+                } else {
+                    None
+                }
+            }
+            Platform::BrickPi3 => {
+                if name.contains("amber:") {
+                    Some((LedSide::Left, LedChannel::Red))
+                } else {
+                    None
+                }
+            }
+            Platform::FatcatLab => None,
+            Platform::PiStorms => {
+                if !name.starts_with("pistorms:") {
+                    None
+                } else if name.contains(":BB:") {
+                    channel(name).map(|channel| (LedSide::Left, channel))
+                } else if name.contains(":BA:") {
+                    channel(name).map(|channel| (LedSide::Right, channel))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Returns the name, input device and key code of every brick button of this platform.
+    ///
+    /// The `GO` button of the PiStorms is named `enter`, so `Ev3Button::is_enter` works on both bricks.
+    pub(crate) fn buttons(&self) -> &'static [(&'static str, &'static str, u32)] {
+        match *self {
+            Platform::Ev3 | Platform::Unknown(_) => &[
+                ("up", EV3_BUTTON_FILE, 103),
+                ("down", EV3_BUTTON_FILE, 108),
+                ("left", EV3_BUTTON_FILE, 105),
+                ("right", EV3_BUTTON_FILE, 106),
+                ("enter", EV3_BUTTON_FILE, 28),
+                ("backspace", EV3_BUTTON_FILE, 14),
+            ],
+            Platform::BrickPi3 => &[],
+            Platform::PiStorms => &[("enter", PISTORMS_BUTTON_FILE, 28)],
+            Platform::FatcatLab => &[
+                ("up", EVB_BUTTON_FILE, 103),
+                ("down", EVB_BUTTON_FILE, 108),
+                ("left", EVB_BUTTON_FILE, 105),
+                ("right", EVB_BUTTON_FILE, 106),
+                ("enter", EVB_BUTTON_FILE, 28),
+                ("backspace", EVB_BUTTON_FILE, 14),
+            ],
+        }
+    }
+}
+
+/// Returns the `BOARD_INFO_MODEL` of every board in `/sys/class/board-info`.
+fn board_info_models() -> Vec<String> {
+    let paths = match fs::read_dir(format!("{}board-info", root_path())) {
+        Ok(paths) => paths,
+        Err(_) => return Vec::new(),
+    };
+
+    paths
+        .filter_map(|path| path.ok())
+        .filter_map(|path| fs::read_to_string(path.path().join("uevent")).ok())
+        .filter_map(|uevent| {
+            uevent
+                .lines()
+                .find(|line| line.starts_with("BOARD_INFO_MODEL="))
+                .map(|line| line["BOARD_INFO_MODEL=".len()..].to_owned())
+        })
+        .collect()
+}
@@ -3,19 +3,23 @@
 
 use std::fs;
 
-use crate::{utils::OrErr, Attribute, Device, Driver, Ev3Error, Ev3Result};
+use crate::{
+    utils::{root_path, OrErr},
+    Attribute, Device, Driver, Ev3Error, Ev3Result,
+};
 
 /// An interface to read data from the system’s power_supply class.
 /// Uses the built-in legoev3-battery if none is specified.
-#[derive(Debug, Clone, Device)]
+#[derive(Debug, Clone)]
 pub struct PowerSupply {
     driver: Driver,
 }
 
 impl PowerSupply {
     /// Create a new instance of `PowerSupply`.
+    /// Matches both the `lego-ev3-battery` and the older `legoev3-battery` name.
     pub fn new() -> Ev3Result<PowerSupply> {
-        let paths = fs::read_dir("/sys/class/power_supply")?;
+        let paths = fs::read_dir(format!("{}power_supply", root_path()))?;
 
         for path in paths {
             let file_name = path?.file_name();
@@ -41,7 +45,7 @@ impl PowerSupply {
 
     /// Always returns System.
     pub fn get_scope(&self) -> Ev3Result<String> {
-        self.get_attribute("zscope").get()
+        self.get_attribute("scope").get()
     }
 
     /// Returns Unknown or Li-ion depending on if the rechargeable battery is present.
@@ -68,4 +72,34 @@ impl PowerSupply {
     pub fn get_voltage_now(&self) -> Ev3Result<i32> {
         self.get_attribute("voltage_now").get()
     }
+
+    /// Returns the battery voltage in volts.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::PowerSupply;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let power_supply = PowerSupply::new()?;
+    ///
+    /// if power_supply.get_voltage_volts()? < 6.5 {
+    ///     println!("Battery low!");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_voltage_volts(&self) -> Ev3Result<f32> {
+        Ok(self.get_voltage_now()? as f32 / 1_000_000.0)
+    }
+}
+
+/// Power supplies are not connected to a port, so `reconnect` is not supported.
+impl Device for PowerSupply {
+    fn get_attribute(&self, name: &str) -> Attribute {
+        self.driver.get_attribute(name)
+    }
+
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        self.driver.try_get_attribute(name)
+    }
 }
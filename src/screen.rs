@@ -6,6 +6,12 @@ use image::{Rgb, RgbImage};
 
 use crate::Ev3Result;
 
+/// Width of a character of `Screen::draw_text` in pixels, including one column of spacing.
+const CHAR_WIDTH: u32 = 6;
+
+/// Height of a line of `Screen::draw_text` in pixels, including one row of spacing.
+pub(crate) const LINE_HEIGHT: u32 = 8;
+
 /// Represents the device screen.
 /// Advanced drawing operations can be perfomed with the `imageproc` crate.
 #[cfg(feature = "screen")]
@@ -55,8 +61,164 @@ impl Screen {
         }
     }
 
+    /// Sets the pixel at (`x`, `y`) to black if `on` is `true` or to white otherwise.
+    /// Pixels outside of the screen are ignored.
+    pub fn set_pixel(&mut self, x: u32, y: u32, on: bool) {
+        if x < self.image.width() && y < self.image.height() {
+            let color = if on { 0 } else { 255 };
+            self.image.put_pixel(x, y, Rgb([color, color, color]));
+        }
+    }
+
+    /// Draws a line from (`x0`, `y0`) to (`x1`, `y1`) using Bresenham's algorithm.
+    /// Pixels outside of the screen are ignored.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Screen;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let mut screen = Screen::new()?;
+    ///
+    /// screen.draw_line(0, 0, screen.xres() - 1, screen.yres() - 1, true);
+    /// screen.draw_rect(10, 10, 40, 20, true);
+    /// screen.update();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw_line(&mut self, x0: u32, y0: u32, x1: u32, y1: u32, on: bool) {
+        let (mut x, mut y) = (i64::from(x0), i64::from(y0));
+        let (x1, y1) = (i64::from(x1), i64::from(y1));
+
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        loop {
+            self.set_pixel(x as u32, y as u32, on);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draws the outline of a rectangle with the top left corner (`x`, `y`).
+    /// Pixels outside of the screen are ignored.
+    pub fn draw_rect(&mut self, x: u32, y: u32, width: u32, height: u32, on: bool) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let right = x.saturating_add(width - 1);
+        let bottom = y.saturating_add(height - 1);
+
+        self.draw_line(x, y, right, y, on);
+        self.draw_line(x, bottom, right, bottom, on);
+        self.draw_line(x, y, x, bottom, on);
+        self.draw_line(right, y, right, bottom, on);
+    }
+
+    /// Fills a rectangle with the top left corner (`x`, `y`).
+    /// Pixels outside of the screen are ignored.
+    pub fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, on: bool) {
+        for py in y..y.saturating_add(height) {
+            for px in x..x.saturating_add(width) {
+                self.set_pixel(px, py, on);
+            }
+        }
+    }
+
+    /// Draws the `text` with the built-in 5x7 pixel font with the top left corner (`x`, `y`).
+    ///
+    /// Every character is 6 pixels wide and every line 8 pixels high, including the spacing.
+    /// The text starts a new line at each `\n`. Characters outside of printable ASCII are drawn as `?`.
+    /// Only the pixels of the glyphs are set, so the background is kept.
+    /// Pixels outside of the screen are ignored.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::Screen;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let mut screen = Screen::new()?;
+    ///
+    /// screen.draw_text(2, 2, "Battery: 7.9V\nMode: follow", true);
+    ///
+    /// // White text on a black bar.
+    /// screen.fill_rect(0, 100, screen.xres(), 10, true);
+    /// screen.draw_text(2, 101, "Press enter", false);
+    /// screen.update();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn draw_text(&mut self, x: u32, y: u32, text: &str, on: bool) {
+        for (row, line) in text.split('\n').enumerate() {
+            let line_y = y.saturating_add(row as u32 * LINE_HEIGHT);
+            for (column, c) in line.chars().enumerate() {
+                let char_x = x.saturating_add(column as u32 * CHAR_WIDTH);
+                self.draw_char(char_x, line_y, c, on);
+            }
+        }
+    }
+
+    /// Draws the `text` like `draw_text`, centered horizontally and vertically on the screen,
+    /// e.g. for menus. Every line is centered on its own.
+    pub fn draw_text_centered(&mut self, text: &str, on: bool) {
+        let (_, height) = Screen::text_size(text);
+        let y = self.yres().saturating_sub(height) / 2;
+
+        for (row, line) in text.split('\n').enumerate() {
+            let (width, _) = Screen::text_size(line);
+            let x = self.xres().saturating_sub(width) / 2;
+            self.draw_text(x, y + row as u32 * LINE_HEIGHT, line, on);
+        }
+    }
+
+    /// Returns the width and height in pixels of the `text` drawn by `draw_text`,
+    /// without the spacing after the last character and line.
+    pub fn text_size(text: &str) -> (u32, u32) {
+        let lines = text.split('\n');
+        let columns = lines
+            .clone()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0) as u32;
+        let rows = lines.count() as u32;
+
+        (
+            (columns * CHAR_WIDTH).saturating_sub(1),
+            (rows * LINE_HEIGHT).saturating_sub(1),
+        )
+    }
+
+    /// Draws a single character of the font with the top left corner (`x`, `y`).
+    fn draw_char(&mut self, x: u32, y: u32, c: char, on: bool) {
+        let index = match c {
+            ' '..='~' => c as usize - ' ' as usize,
+            _ => '?' as usize - ' ' as usize,
+        };
+
+        for (column, bits) in FONT[index].iter().enumerate() {
+            for row in 0..7 {
+                if bits & (1 << row) != 0 {
+                    self.set_pixel(x.saturating_add(column as u32), y.saturating_add(row), on);
+                }
+            }
+        }
+    }
+
     fn update_1bpp(&mut self) {
-        let mut buffer = vec![0u8; ((self.xres() * self.yres() + 7) / 8) as usize];
+        let mut buffer = vec![0u8; (self.xres() * self.yres()).div_ceil(8) as usize];
 
         let mut byte: usize = 0;
         let mut bit: u8 = 0x80;
@@ -65,7 +227,7 @@ impl Screen {
 
             buffer[byte] |= if sum >= 0x30 { bit } else { 0x00 };
 
-            bit = bit >> 1;
+            bit >>= 1;
             if bit == 0 {
                 byte += 1;
                 bit = 0x80;
@@ -120,3 +282,104 @@ impl Screen {
         }
     }
 }
+
+/// 5x7 pixel font of the printable ASCII characters from ` ` to `~`.
+/// Every glyph consists of 5 columns, the least significant bit is the top row.
+#[cfg(feature = "screen")]
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // `!`
+    [0x00, 0x07, 0x00, 0x07, 0x00], // `"`
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // `#`
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // `$`
+    [0x23, 0x13, 0x08, 0x64, 0x62], // `%`
+    [0x36, 0x49, 0x55, 0x22, 0x50], // `&`
+    [0x00, 0x05, 0x03, 0x00, 0x00], // `'`
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // `(`
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // `)`
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // `*`
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // `+`
+    [0x00, 0x50, 0x30, 0x00, 0x00], // `,`
+    [0x08, 0x08, 0x08, 0x08, 0x08], // `-`
+    [0x00, 0x60, 0x60, 0x00, 0x00], // `.`
+    [0x20, 0x10, 0x08, 0x04, 0x02], // `/`
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // `0`
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // `1`
+    [0x42, 0x61, 0x51, 0x49, 0x46], // `2`
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // `3`
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // `4`
+    [0x27, 0x45, 0x45, 0x45, 0x39], // `5`
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // `6`
+    [0x01, 0x71, 0x09, 0x05, 0x03], // `7`
+    [0x36, 0x49, 0x49, 0x49, 0x36], // `8`
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // `9`
+    [0x00, 0x36, 0x36, 0x00, 0x00], // `:`
+    [0x00, 0x56, 0x36, 0x00, 0x00], // `;`
+    [0x08, 0x14, 0x22, 0x41, 0x00], // `<`
+    [0x14, 0x14, 0x14, 0x14, 0x14], // `=`
+    [0x00, 0x41, 0x22, 0x14, 0x08], // `>`
+    [0x02, 0x01, 0x51, 0x09, 0x06], // `?`
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // `@`
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // `A`
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // `B`
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // `C`
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // `D`
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // `E`
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // `F`
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // `G`
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // `H`
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // `I`
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // `J`
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // `K`
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // `L`
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // `M`
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // `N`
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // `O`
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // `P`
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // `Q`
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // `R`
+    [0x46, 0x49, 0x49, 0x49, 0x31], // `S`
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // `T`
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // `U`
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // `V`
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // `W`
+    [0x63, 0x14, 0x08, 0x14, 0x63], // `X`
+    [0x03, 0x04, 0x78, 0x04, 0x03], // `Y`
+    [0x61, 0x51, 0x49, 0x45, 0x43], // `Z`
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // `[`
+    [0x02, 0x04, 0x08, 0x10, 0x20], // `\`
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // `]`
+    [0x04, 0x02, 0x01, 0x02, 0x04], // `^`
+    [0x40, 0x40, 0x40, 0x40, 0x40], // `_`
+    [0x00, 0x01, 0x02, 0x04, 0x00], // ```
+    [0x20, 0x54, 0x54, 0x54, 0x78], // `a`
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // `b`
+    [0x38, 0x44, 0x44, 0x44, 0x20], // `c`
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // `d`
+    [0x38, 0x54, 0x54, 0x54, 0x18], // `e`
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // `f`
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // `g`
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // `h`
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // `i`
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // `j`
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // `k`
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // `l`
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // `m`
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // `n`
+    [0x38, 0x44, 0x44, 0x44, 0x38], // `o`
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // `p`
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // `q`
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // `r`
+    [0x48, 0x54, 0x54, 0x54, 0x20], // `s`
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // `t`
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // `u`
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // `v`
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // `w`
+    [0x44, 0x28, 0x10, 0x28, 0x44], // `x`
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // `y`
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // `z`
+    [0x00, 0x08, 0x36, 0x41, 0x00], // `{`
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // `|`
+    [0x00, 0x41, 0x36, 0x08, 0x00], // `}`
+    [0x10, 0x08, 0x08, 0x10, 0x08], // `~`
+];
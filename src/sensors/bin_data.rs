@@ -0,0 +1,198 @@
+//! Decoding of the raw `bin_data` attribute of sensors.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Ev3Error, Ev3Result};
+
+/// Format of the values in the `bin_data` attribute of a sensor mode, see `Sensor::get_bin_data_format`.
+///
+/// Multi-byte values are little endian, except for the explicit big endian formats.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum BinDataFormat {
+    /// Unsigned 8-bit integer (`u8`).
+    U8,
+    /// Signed 8-bit integer (`s8`).
+    S8,
+    /// Unsigned 16-bit integer, little endian (`u16`).
+    U16,
+    /// Signed 16-bit integer, little endian (`s16`).
+    S16,
+    /// Signed 16-bit integer, big endian (`s16_be`).
+    S16Be,
+    /// Signed 32-bit integer, little endian (`s32`).
+    S32,
+    /// Signed 32-bit integer, big endian (`s32_be`).
+    S32Be,
+    /// IEEE 754 32-bit floating point, little endian (`float`).
+    Float,
+}
+
+impl BinDataFormat {
+    /// Returns the sysfs value of the format, e.g. `s16_be`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BinDataFormat::U8 => "u8",
+            BinDataFormat::S8 => "s8",
+            BinDataFormat::U16 => "u16",
+            BinDataFormat::S16 => "s16",
+            BinDataFormat::S16Be => "s16_be",
+            BinDataFormat::S32 => "s32",
+            BinDataFormat::S32Be => "s32_be",
+            BinDataFormat::Float => "float",
+        }
+    }
+
+    /// Returns the size of one value in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            BinDataFormat::U8 | BinDataFormat::S8 => 1,
+            BinDataFormat::U16 | BinDataFormat::S16 | BinDataFormat::S16Be => 2,
+            BinDataFormat::S32 | BinDataFormat::S32Be | BinDataFormat::Float => 4,
+        }
+    }
+
+    /// Decodes the first value of `bytes`, which must contain at least `size()` bytes.
+    fn decode(self, bytes: &[u8]) -> BinDataValue {
+        match self {
+            BinDataFormat::U8 => BinDataValue::Integer(i64::from(bytes[0])),
+            BinDataFormat::S8 => BinDataValue::Integer(i64::from(bytes[0] as i8)),
+            BinDataFormat::U16 => {
+                BinDataValue::Integer(i64::from(u16::from_le_bytes([bytes[0], bytes[1]])))
+            }
+            BinDataFormat::S16 => {
+                BinDataValue::Integer(i64::from(i16::from_le_bytes([bytes[0], bytes[1]])))
+            }
+            BinDataFormat::S16Be => {
+                BinDataValue::Integer(i64::from(i16::from_be_bytes([bytes[0], bytes[1]])))
+            }
+            BinDataFormat::S32 => BinDataValue::Integer(i64::from(i32::from_le_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ]))),
+            BinDataFormat::S32Be => BinDataValue::Integer(i64::from(i32::from_be_bytes([
+                bytes[0], bytes[1], bytes[2], bytes[3],
+            ]))),
+            BinDataFormat::Float => {
+                BinDataValue::Float(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            }
+        }
+    }
+
+    /// Decodes the first `count` values of `bytes` to the type `T`.
+    ///
+    /// Returns `Ev3Error::InternalError` if `bytes` is too short or a value does not fit into `T`.
+    pub(crate) fn decode_all<T>(self, bytes: &[u8], count: usize) -> Ev3Result<Vec<T>>
+    where
+        T: BinDataType,
+    {
+        let size = self.size();
+        if bytes.len() < count * size {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "`bin_data` has {} bytes, expected {} values of format `{}`",
+                    bytes.len(),
+                    count,
+                    self
+                ),
+            });
+        }
+
+        bytes
+            .chunks(size)
+            .take(count)
+            .map(|chunk| {
+                T::from_bin_data(self.decode(chunk)).ok_or_else(|| Ev3Error::InternalError {
+                    msg: format!(
+                        "Value of format `{}` does not fit into `{}`",
+                        self,
+                        std::any::type_name::<T>()
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for BinDataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for BinDataFormat {
+    type Err = Ev3Error;
+
+    /// Parses a sysfs format like `s16_be`.
+    ///
+    /// Returns `Ev3Error::InternalError` for unknown formats.
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "u8" => Ok(BinDataFormat::U8),
+            "s8" => Ok(BinDataFormat::S8),
+            "u16" => Ok(BinDataFormat::U16),
+            "s16" => Ok(BinDataFormat::S16),
+            "s16_be" => Ok(BinDataFormat::S16Be),
+            "s32" => Ok(BinDataFormat::S32),
+            "s32_be" => Ok(BinDataFormat::S32Be),
+            "float" => Ok(BinDataFormat::Float),
+            _ => Err(Ev3Error::InternalError {
+                msg: format!("`{}` is not a valid BinDataFormat", format),
+            }),
+        }
+    }
+}
+
+/// A decoded value of `bin_data`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BinDataValue {
+    /// Value of one of the integer formats.
+    Integer(i64),
+    /// Value of the `float` format.
+    Float(f32),
+}
+
+/// Types that values of `bin_data` can be decoded to, see `Sensor::get_bin_data_typed`.
+///
+/// Implemented for the integer types, which accept integer values in their range,
+/// and for `f32` and `f64`, which accept all values.
+pub trait BinDataType: Sized {
+    /// Converts the decoded `value`, `None` if it does not fit into the type.
+    fn from_bin_data(value: BinDataValue) -> Option<Self>;
+}
+
+macro_rules! impl_bin_data_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl BinDataType for $integer {
+                fn from_bin_data(value: BinDataValue) -> Option<Self> {
+                    match value {
+                        BinDataValue::Integer(value) => <$integer>::try_from(value).ok(),
+                        BinDataValue::Float(_) => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_bin_data_integer!(u8, i8, u16, i16, u32, i32, i64);
+
+impl BinDataType for f32 {
+    fn from_bin_data(value: BinDataValue) -> Option<Self> {
+        match value {
+            BinDataValue::Integer(value) => Some(value as f32),
+            BinDataValue::Float(value) => Some(value),
+        }
+    }
+}
+
+impl BinDataType for f64 {
+    fn from_bin_data(value: BinDataValue) -> Option<Self> {
+        match value {
+            BinDataValue::Integer(value) => Some(value as f64),
+            BinDataValue::Float(value) => Some(f64::from(value)),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! LEGO EV3 color sensor.
 
-use super::{Sensor, SensorPort};
+use super::{sensor::switch_mode, Sensor, SensorPort};
 use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 
 /// LEGO EV3 color sensor.
@@ -70,6 +70,25 @@ impl ColorSensor {
         self.get_value0()
     }
 
+    /// A measurement of the reflected light intensity, as a percentage in the range 0-100.
+    ///
+    /// Switches the sensor to `COL-REFLECT` mode if necessary and waits until the mode switch took effect.
+    /// The EV3 color sensor reports whole percent values, while the NXT `LightSensor` has one decimal place.
+    pub fn get_reflected_light_intensity(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_COL_REFLECT)?;
+
+        Ok(self.get_value0()? as f32)
+    }
+
+    /// A measurement of the ambient light intensity, as a percentage in the range 0-100.
+    ///
+    /// Switches the sensor to `COL-AMBIENT` mode if necessary and waits until the mode switch took effect.
+    pub fn get_ambient_light_intensity(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_COL_AMBIENT)?;
+
+        Ok(self.get_value0()? as f32)
+    }
+
     /// Red component of the detected color, in the range 0-1020.
     pub fn get_red(&self) -> Ev3Result<i32> {
         self.get_value0()
@@ -86,7 +105,12 @@ impl ColorSensor {
     }
 
     /// Red, green and blue componets of the detected color, each in the range 0-1020
+    ///
+    /// Switches the sensor to `RGB-RAW` mode if necessary. After a mode switch this waits
+    /// until the `mode` attribute reports the new mode, so no stale values of the previous mode are returned.
     pub fn get_rgb(&self) -> Ev3Result<(i32, i32, i32)> {
+        switch_mode(self, Self::MODE_RGB_RAW)?;
+
         let red = self.get_red()?;
         let green = self.get_green()?;
         let blue = self.get_blue()?;
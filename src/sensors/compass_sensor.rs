@@ -52,11 +52,11 @@ impl CompassSensor {
         Ok(rel_rot)
     }
 
-    /// calibration:
-    /// start the calibration by start_calibration()
-    /// turn the robot 360 degrees
-    /// end the calibration by stop_calibration()
-    /// attention: if calibration has not finished, the get_rotation method always returns -258
+    // calibration:
+    // start the calibration by start_calibration()
+    // turn the robot 360 degrees
+    // end the calibration by stop_calibration()
+    // attention: if calibration has not finished, the get_rotation method always returns -258
 
     /// starts the calibration
     pub fn start_calibration(&self) -> Ev3Result<()> {
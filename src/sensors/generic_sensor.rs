@@ -0,0 +1,113 @@
+//! Generic sensor for unsupported or third-party sensors.
+
+use super::{Sensor, SensorPort};
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result, Port};
+
+/// Generic sensor of the `lego-sensor` class without any sensor specific assumptions.
+///
+/// It accepts every driver, so it can be used for sensors without a typed representation.
+/// All values and modes are accessible through the `Sensor` trait.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Device;
+/// use ev3dev_lang_rust::sensors::{GenericSensor, Sensor, SensorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = GenericSensor::get(SensorPort::In1)?;
+///
+/// println!("Driver: {}", sensor.get_driver_name()?);
+/// println!("Modes: {:?}", sensor.get_modes()?);
+///
+/// for index in 0..sensor.get_num_values()? {
+///     println!("value{}: {}", index, sensor.get_value(index as u8)?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Sensor)]
+pub struct GenericSensor {
+    driver: Driver,
+}
+
+impl GenericSensor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    /// Create a `GenericSensor` on top of the given `driver`, e.g. a `Driver::with_root` for a mock device.
+    #[cfg(feature = "mock")]
+    pub fn from_driver(driver: Driver) -> Self {
+        Self::new(driver)
+    }
+
+    /// Try to get the sensor on the given port, regardless of its driver.
+    pub fn get(port: SensorPort) -> Ev3Result<Self> {
+        let name = Driver::find_name_by_port("lego-sensor", &port).map_err(Self::map_error)?;
+
+        Ok(Self::new(Driver::new("lego-sensor", &name)))
+    }
+
+    /// Try to find the sensor with the given `driver_name`.
+    /// Only returns a sensor if there is exactly one connected, `Ev3Error::NotConnected` otherwise.
+    pub fn find_by_driver(driver_name: &str) -> Ev3Result<Self> {
+        let name =
+            Driver::find_name_by_driver("lego-sensor", &[driver_name]).map_err(Self::map_error)?;
+
+        Ok(Self::new(Driver::new("lego-sensor", &name)))
+    }
+
+    /// Extract list of all connected sensors, regardless of their driver, ordered by port address.
+    pub fn list() -> Ev3Result<Vec<Self>> {
+        let mut devices: Vec<_> = crate::devices::scan()?
+            .into_iter()
+            .filter(|device| device.class_name == "lego-sensor")
+            .collect();
+        devices.sort_by_cached_key(|device| crate::driver::address_order(&device.address));
+
+        Ok(devices
+            .into_iter()
+            .map(|device| Self::new(Driver::new("lego-sensor", &device.name)))
+            .collect())
+    }
+
+    /// Replaces the device name and formats the port names of lookup errors.
+    fn map_error(e: Ev3Error) -> Ev3Error {
+        match e {
+            Ev3Error::NotConnected { device: _, port } => Ev3Error::NotConnected {
+                device: "GenericSensor".to_owned(),
+                port,
+            },
+            Ev3Error::MultipleMatches {
+                device: _,
+                ports,
+                names,
+            } => Ev3Error::MultipleMatches {
+                device: "GenericSensor".to_owned(),
+                ports: ports
+                    .iter()
+                    .map(|item| SensorPort::format_name(item))
+                    .collect(),
+                names,
+            },
+            e => e,
+        }
+    }
+}
+
+impl Device for GenericSensor {
+    fn get_attribute(&self, name: &str) -> Attribute {
+        self.driver.get_attribute(name)
+    }
+
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        self.driver.try_get_attribute(name)
+    }
+
+    /// Re-resolves the sensor at the `port`, regardless of its driver, like `get`.
+    fn reconnect(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        self.driver
+            .reconnect_any_driver(port)
+            .map_err(Self::map_error)
+    }
+}
@@ -2,6 +2,17 @@
 
 use super::{Sensor, SensorPort};
 use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Time to wait between the mode switches of a reset.
+const RESET_MODE_DELAY: Duration = Duration::from_millis(100);
+
+/// Maximal time to wait for the angle to read zero after a reset.
+const RESET_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Maximal absolute angle that is considered as zero after a reset.
+const RESET_ANGLE_TOLERANCE: i32 = 1;
 
 /// LEGO EV3 gyro sensor.
 #[derive(Debug, Clone, Device, Sensor)]
@@ -97,4 +108,49 @@ impl GyroSensor {
             }),
         }
     }
+
+    /// Gets the angle and the rotational speed value as a tuple.
+    /// Both values are read in `GYRO-G&A` mode, so no mode switch is necessary between the reads.
+    /// Fails if it has been set in the wrong mode
+    pub fn get_angle_and_rate(&self) -> Ev3Result<(i32, i32)> {
+        match self.get_mode()?.as_ref() {
+            GyroSensor::MODE_GYRO_G_AND_A => Ok((self.get_value0()?, self.get_value1()?)),
+            mode => Ev3Result::Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Cannot get angle and rotational speed while in {} mode",
+                    mode
+                ),
+            }),
+        }
+    }
+
+    /// Resets the angle and the accumulated drift of the sensor.
+    ///
+    /// The sensor is switched to `GYRO-RATE` and `GYRO-CAL` mode and afterwards back to the previous mode.
+    /// If the previous mode reports an angle this waits until the angle reads (nearly) zero.
+    /// The sensor must not move during the reset.
+    pub fn reset(&self) -> Ev3Result<()> {
+        let mode = self.get_mode()?;
+
+        self.set_mode_gyro_rate()?;
+        thread::sleep(RESET_MODE_DELAY);
+        self.set_mode_gyro_cal()?;
+        thread::sleep(RESET_MODE_DELAY);
+        self.set_mode(&mode)?;
+
+        if mode != GyroSensor::MODE_GYRO_ANG && mode != GyroSensor::MODE_GYRO_G_AND_A {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        while self.get_angle()?.abs() > RESET_ANGLE_TOLERANCE {
+            if start.elapsed() >= RESET_TIMEOUT {
+                return Err(Ev3Error::InternalError {
+                    msg: "Gyro sensor angle did not reset to zero".to_owned(),
+                });
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        Ok(())
+    }
 }
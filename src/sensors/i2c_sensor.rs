@@ -0,0 +1,172 @@
+//! Raw register access to NXT I2C sensors.
+
+use super::{Sensor, SensorPort};
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result, Platform, Port};
+
+/// Sensor of the `lego-sensor` class that is connected by I2C, e.g. with the generic `nxt-i2c-sensor` driver.
+///
+/// It accepts every driver at an I2C address of the port (`ev3-ports:in1:i2c1`) and gives raw access
+/// to the registers of the sensor through the `direct` attribute. The byte offset of a read or write
+/// selects the register, so third-party sensors can be supported without a typed representation.
+/// The mode, poll interval and values are accessible through the `Sensor` trait as usual.
+///
+/// Instead of the `direct` attribute the I2C bus can be opened through its `i2c-dev` node, see `get_i2c_dev_path`.
+/// On the EV3 the input ports `in1` to `in4` are the I2C adapters `3` to `6`,
+/// so a sensor at `in1` is at `/dev/i2c-3` with the address of `get_i2c_address`.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{I2cSensor, SensorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = I2cSensor::get(SensorPort::In1)?;
+///
+/// // Read the 8 byte vendor id of a NXT I2C sensor.
+/// let mut vendor_id = [0u8; 8];
+/// sensor.read_register(0x08, &mut vendor_id)?;
+/// println!("Vendor: {}", String::from_utf8_lossy(&vendor_id));
+///
+/// // Write a command register.
+/// sensor.write_register(0x41, &[0x43])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Sensor)]
+pub struct I2cSensor {
+    driver: Driver,
+}
+
+impl I2cSensor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    /// Try to get the I2C sensor on the given port, regardless of its driver and I2C address.
+    /// Returns `Ev3Error::MultipleMatches` if several I2C sensors are connected to the port, see `get_with_address`.
+    pub fn get(port: SensorPort) -> Ev3Result<Self> {
+        Self::find_on_port(port, None)
+    }
+
+    /// Try to get the I2C sensor with the 7-bit I2C `address` on the given port, regardless of its driver.
+    pub fn get_with_address(port: SensorPort, address: u8) -> Ev3Result<Self> {
+        Self::find_on_port(port, Some(address))
+    }
+
+    /// Returns the only sensor at an I2C address of the `port`, optionally only at the given `address`.
+    fn find_on_port(port: SensorPort, address: Option<u8>) -> Ev3Result<Self> {
+        let name = Self::find_name_on_port(&port, address)?;
+        Ok(Self::new(Driver::new("lego-sensor", &name)))
+    }
+
+    /// Returns the sysfs name of the only sensor at an I2C address of the `port`, optionally only at the given `address`.
+    fn find_name_on_port(port: &dyn Port, address: Option<u8>) -> Ev3Result<String> {
+        let port_address = port.address();
+        let mut devices: Vec<_> = crate::devices::scan()?
+            .into_iter()
+            .filter(|device| device.class_name == "lego-sensor")
+            .filter(|device| {
+                let i2c_address = parse_i2c_address(&device.address, &port_address);
+                i2c_address.is_some() && (address.is_none() || address == i2c_address)
+            })
+            .collect();
+        devices.sort_by_cached_key(|device| crate::driver::address_order(&device.address));
+
+        match devices.len() {
+            0 => Err(Ev3Error::NotConnected {
+                device: "I2cSensor".to_owned(),
+                port: Some(port_address),
+            }),
+            1 => Ok(devices.remove(0).name),
+            _ => Err(Ev3Error::MultipleMatches {
+                device: "I2cSensor".to_owned(),
+                ports: devices
+                    .iter()
+                    .map(|device| device.address.clone())
+                    .collect(),
+                names: devices.into_iter().map(|device| device.name).collect(),
+            }),
+        }
+    }
+
+    /// Returns the 7-bit I2C address of the sensor, parsed from the `address` attribute (`ev3-ports:in1:i2c1`).
+    pub fn get_i2c_address(&self) -> Ev3Result<u8> {
+        let address = self.get_address()?;
+        address
+            .rsplit(':')
+            .next()
+            .and_then(|part| part.strip_prefix("i2c"))
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("Address `{}` is not an I2C address", address),
+            })
+    }
+
+    /// Returns the path of the `i2c-dev` node of the bus the sensor is connected to, e.g. `/dev/i2c-3` for `in1`.
+    ///
+    /// The mapping of ports to I2C adapters is only known for the EV3,
+    /// other platforms return an `Ev3Error::InternalError`.
+    pub fn get_i2c_dev_path(&self) -> Ev3Result<String> {
+        if Platform::detect() != Platform::Ev3 {
+            return Err(Ev3Error::InternalError {
+                msg: "The I2C bus of the input ports is only known for the EV3".to_owned(),
+            });
+        }
+
+        let address = self.get_address()?;
+        let bus = [
+            SensorPort::In1,
+            SensorPort::In2,
+            SensorPort::In3,
+            SensorPort::In4,
+        ]
+        .iter()
+        .position(|port| address.split(':').any(|part| part == port.address()))
+        .ok_or_else(|| Ev3Error::InternalError {
+            msg: format!("Address `{}` is not an input port", address),
+        })?;
+
+        Ok(format!("/dev/i2c-{}", bus + 3))
+    }
+
+    /// Reads `buffer.len()` bytes starting at the `register` of the sensor through the `direct` attribute.
+    /// Returns the number of bytes read.
+    pub fn read_register(&self, register: u8, buffer: &mut [u8]) -> Ev3Result<usize> {
+        self.get_direct()?
+            .read_bytes_at(u64::from(register), buffer)
+    }
+
+    /// Writes the `bytes` starting at the `register` of the sensor through the `direct` attribute.
+    pub fn write_register(&self, register: u8, bytes: &[u8]) -> Ev3Result<()> {
+        self.get_direct()?
+            .write_bytes_at(u64::from(register), bytes)
+    }
+
+    /// Returns the `direct` attribute, which only exists for I2C sensors.
+    fn get_direct(&self) -> Ev3Result<Attribute> {
+        self.try_get_attribute("direct")
+    }
+}
+
+impl Device for I2cSensor {
+    fn get_attribute(&self, name: &str) -> Attribute {
+        self.driver.get_attribute(name)
+    }
+
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        self.driver.try_get_attribute(name)
+    }
+
+    /// Re-resolves the sensor at an I2C address of the `port`, regardless of its driver and I2C address, like `get`.
+    fn reconnect(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        let name = Self::find_name_on_port(port, None)?;
+        self.driver = Driver::new("lego-sensor", &name);
+        Ok(())
+    }
+}
+
+/// Returns the I2C address of a sysfs `address` like `ev3-ports:in1:i2c1` at the given `port_address`.
+fn parse_i2c_address(address: &str, port_address: &str) -> Option<u8> {
+    let mut parts = address.trim().split(':');
+    parts.find(|part| *part == port_address)?;
+    parts.next()?.strip_prefix("i2c")?.parse().ok()
+}
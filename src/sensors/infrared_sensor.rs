@@ -1,6 +1,6 @@
 //! LEGO EV3 infrared sensor.
 
-use super::{Sensor, SensorPort};
+use super::{sensor::switch_mode, Sensor, SensorPort};
 use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 use std::cell::RefCell;
 use std::collections::HashSet;
@@ -73,6 +73,117 @@ impl InfraredSensor {
     pub fn get_distance(&self) -> Ev3Result<i32> {
         self.get_value0()
     }
+
+    /// Returns heading (-25, 25) and distance (0, 100) to the beacon on the given `channel` (1-4),
+    /// or `None` if no beacon is detected on this channel.
+    /// Switches to the `IR-SEEK` mode if necessary.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::InfraredSensor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = InfraredSensor::find()?;
+    ///
+    /// match sensor.get_beacon_heading_and_distance(1)? {
+    ///     Some((heading, distance)) => println!("Beacon at {} with distance {}", heading, distance),
+    ///     None => println!("No beacon found"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_beacon_heading_and_distance(&self, channel: u8) -> Ev3Result<Option<(i32, i32)>> {
+        switch_mode(self, Self::MODE_IR_SEEK)?;
+
+        let channel = u8::max(1, u8::min(4, channel)) - 1;
+        let heading = self.get_value(channel * 2)?;
+        let distance = self.get_value(channel * 2 + 1)?;
+
+        if distance == BEACON_NOT_DETECTED {
+            Ok(None)
+        } else {
+            Ok(Some((heading, distance)))
+        }
+    }
+
+    /// Returns the currently pressed buttons of the remote control on the given `channel` (1-4).
+    /// Switches to the `IR-REMOTE` mode if necessary.
+    pub fn get_remote_command(&self, channel: u8) -> Ev3Result<RemoteButton> {
+        switch_mode(self, Self::MODE_IR_REMOTE)?;
+
+        let channel = u8::max(1, u8::min(4, channel)) - 1;
+        Ok(RemoteButton::from_value(self.get_value(channel)?))
+    }
+}
+
+/// Distance value of the `IR-SEEK` mode if no beacon is detected.
+const BEACON_NOT_DETECTED: i32 = -128;
+
+/// Button combinations of the EV3 remote control as reported by the `IR-REMOTE` mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RemoteButton {
+    /// No button is pressed.
+    None,
+    /// The red up button is pressed.
+    RedUp,
+    /// The red down button is pressed.
+    RedDown,
+    /// The blue up button is pressed.
+    BlueUp,
+    /// The blue down button is pressed.
+    BlueDown,
+    /// The red up and the blue up buttons are pressed.
+    RedUpBlueUp,
+    /// The red up and the blue down buttons are pressed.
+    RedUpBlueDown,
+    /// The red down and the blue up buttons are pressed.
+    RedDownBlueUp,
+    /// The red down and the blue down buttons are pressed.
+    RedDownBlueDown,
+    /// The beacon mode is active.
+    Beacon,
+    /// The red up and the red down buttons are pressed.
+    RedUpRedDown,
+    /// The blue up and the blue down buttons are pressed.
+    BlueUpBlueDown,
+}
+
+impl RemoteButton {
+    /// Converts the raw channel value of the `IR-REMOTE` mode.
+    pub fn from_value(value: i32) -> RemoteButton {
+        match value {
+            1 => RemoteButton::RedUp,
+            2 => RemoteButton::RedDown,
+            3 => RemoteButton::BlueUp,
+            4 => RemoteButton::BlueDown,
+            5 => RemoteButton::RedUpBlueUp,
+            6 => RemoteButton::RedUpBlueDown,
+            7 => RemoteButton::RedDownBlueUp,
+            8 => RemoteButton::RedDownBlueDown,
+            9 => RemoteButton::Beacon,
+            10 => RemoteButton::RedUpRedDown,
+            11 => RemoteButton::BlueUpBlueDown,
+            _ => RemoteButton::None,
+        }
+    }
+
+    /// Returns the names of the pressed buttons, e.g. `["red_up", "blue_down"]`.
+    pub fn button_names(self) -> &'static [&'static str] {
+        match self {
+            RemoteButton::None => &[],
+            RemoteButton::RedUp => &["red_up"],
+            RemoteButton::RedDown => &["red_down"],
+            RemoteButton::BlueUp => &["blue_up"],
+            RemoteButton::BlueDown => &["blue_down"],
+            RemoteButton::RedUpBlueUp => &["red_up", "blue_up"],
+            RemoteButton::RedUpBlueDown => &["red_up", "blue_down"],
+            RemoteButton::RedDownBlueUp => &["red_down", "blue_up"],
+            RemoteButton::RedDownBlueDown => &["red_down", "blue_down"],
+            RemoteButton::Beacon => &["beacon"],
+            RemoteButton::RedUpRedDown => &["red_up", "red_down"],
+            RemoteButton::BlueUpBlueDown => &["blue_up", "blue_down"],
+        }
+    }
 }
 
 struct RemoteControlHelper {
@@ -159,48 +270,8 @@ impl RemoteControl {
             helper.last_buttons = buttons;
 
             helper.pressed_buttons.clear();
-
-            match buttons {
-                1 => {
-                    helper.pressed_buttons.insert("red_up".to_owned());
-                }
-                2 => {
-                    helper.pressed_buttons.insert("red_down".to_owned());
-                }
-                3 => {
-                    helper.pressed_buttons.insert("blue_up".to_owned());
-                }
-                4 => {
-                    helper.pressed_buttons.insert("blue_down".to_owned());
-                }
-                5 => {
-                    helper.pressed_buttons.insert("red_up".to_owned());
-                    helper.pressed_buttons.insert("blue_up".to_owned());
-                }
-                6 => {
-                    helper.pressed_buttons.insert("red_up".to_owned());
-                    helper.pressed_buttons.insert("blue_down".to_owned());
-                }
-                7 => {
-                    helper.pressed_buttons.insert("red_down".to_owned());
-                    helper.pressed_buttons.insert("blue_up".to_owned());
-                }
-                8 => {
-                    helper.pressed_buttons.insert("red_down".to_owned());
-                    helper.pressed_buttons.insert("blue_down".to_owned());
-                }
-                9 => {
-                    helper.pressed_buttons.insert("beacon".to_owned());
-                }
-                10 => {
-                    helper.pressed_buttons.insert("red_up".to_owned());
-                    helper.pressed_buttons.insert("red_down".to_owned());
-                }
-                11 => {
-                    helper.pressed_buttons.insert("blue_up".to_owned());
-                    helper.pressed_buttons.insert("blue_down".to_owned());
-                }
-                _ => {}
+            for name in RemoteButton::from_value(buttons).button_names() {
+                helper.pressed_buttons.insert((*name).to_owned());
             }
         }
         Ok(())
@@ -1,10 +1,14 @@
-//! LEGO EV3 light sensor.
+//! LEGO NXT light sensor.
 
-use super::{Sensor, SensorPort};
+use super::{sensor::switch_mode, Sensor, SensorPort};
 use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 use std::cell::Cell;
 
-/// LEGO EV3 light sensor.
+/// LEGO NXT light sensor.
+///
+/// The raw values of both modes are in the range 0-1000 with one decimal place,
+/// `get_reflected_light_intensity` and `get_ambient_light_intensity` scale them to 0.0-100.0 percent.
+/// The EV3 `ColorSensor` reports whole percent values in the range 0-100 instead.
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct LightSensor {
     driver: Driver,
@@ -49,8 +53,12 @@ impl LightSensor {
         self.get_value0()
     }
 
-    /// A measurement of the reflected light intensity, as a percentage.
+    /// A measurement of the reflected light intensity, as a percentage in the range 0.0-100.0.
+    ///
+    /// Switches the sensor to `REFLECT` mode if necessary and waits until the mode switch took effect.
     pub fn get_reflected_light_intensity(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_REFLECT)?;
+
         let scale_field = self.reflect_scale.get();
         let scale = match scale_field {
             Some(s) => s,
@@ -65,8 +73,12 @@ impl LightSensor {
         Ok((self.get_value0()? as f32) * scale)
     }
 
-    /// A measurement of the ambient light intensity, as a percentage.
+    /// A measurement of the ambient light intensity, as a percentage in the range 0.0-100.0.
+    ///
+    /// Switches the sensor to `AMBIENT` mode if necessary and waits until the mode switch took effect.
     pub fn get_ambient_light_intensity(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_AMBIENT)?;
+
         let scale_field = self.ambient_scale.get();
         let scale = match scale_field {
             Some(s) => s,
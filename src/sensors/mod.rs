@@ -1,7 +1,12 @@
 //! # Container module for sensor types
 
+mod bin_data;
+pub use self::bin_data::{BinDataFormat, BinDataType, BinDataValue};
+
 mod sensor;
-pub use self::sensor::Sensor;
+pub use self::sensor::{
+    get_mode_settle_delay, set_mode_settle_delay, SampleIter, Sensor, SensorSnapshot, MIN_POLL_MS,
+};
 
 mod color_sensor;
 pub use self::color_sensor::ColorSensor;
@@ -21,18 +26,36 @@ pub use self::gyro_sensor::GyroSensor;
 mod infrared_sensor;
 pub use self::infrared_sensor::BeaconSeeker;
 pub use self::infrared_sensor::InfraredSensor;
+pub use self::infrared_sensor::RemoteButton;
 pub use self::infrared_sensor::RemoteControl;
 
+mod sound_sensor;
+pub use self::sound_sensor::SoundSensor;
+
+mod temperature_sensor;
+pub use self::temperature_sensor::TemperatureSensor;
+
 mod touch_sensor;
 pub use self::touch_sensor::TouchSensor;
 
 mod ultrasonic_sensor;
 pub use self::ultrasonic_sensor::UltrasonicSensor;
 
-use crate::Port;
+mod generic_sensor;
+pub use self::generic_sensor::GenericSensor;
+
+mod i2c_sensor;
+pub use self::i2c_sensor::I2cSensor;
+
+mod sensor_kind;
+pub use self::sensor_kind::SensorKind;
 
-/// EV3 ports `in1` to `in4`
-#[derive(Debug, Copy, Clone)]
+use std::str::FromStr;
+
+use crate::{BrickPi3Port, Ev3Error, Ev3Result, Port};
+
+/// EV3 ports `in1` to `in4` and the input ports of stacked BrickPi3 boards
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum SensorPort {
     /// EV3 `in1` port
     In1,
@@ -42,9 +65,45 @@ pub enum SensorPort {
     In3,
     /// EV3 `in4` port
     In4,
+    /// Input port of a stacked BrickPi3 board.
+    ///
+    /// The ports of all layers are numbered consecutively, so the first port of the second layer
+    /// is `S5` (`spi0.1:S5`).
+    BrickPi3(BrickPi3Port),
 }
 
 impl SensorPort {
+    /// Returns the BrickPi3 port with the same number as this port at the `layer` of the stack,
+    /// e.g. `SensorPort::In1.on_layer(2)` is the port `S5`.
+    ///
+    /// Returns `Ev3Error::InternalError` if the `layer` is not `1` to `BRICKPI3_MAX_LAYERS`.
+    ///
+    /// # Example
+    /// ```
+    /// use ev3dev_lang_rust::sensors::SensorPort;
+    /// use ev3dev_lang_rust::{BrickPi3Port, Port};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let port = SensorPort::In1.on_layer(2)?;
+    /// assert_eq!(port, SensorPort::BrickPi3(BrickPi3Port::new(2, 1)?));
+    /// assert_eq!(port.address(), "S5");
+    /// assert_eq!(port.layer(), Some(2));
+    ///
+    /// assert!(SensorPort::In1.on_layer(0).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_layer(self, layer: u8) -> Ev3Result<SensorPort> {
+        let port = match self {
+            SensorPort::In1 => 1,
+            SensorPort::In2 => 2,
+            SensorPort::In3 => 3,
+            SensorPort::In4 => 4,
+            SensorPort::BrickPi3(port) => port.port(),
+        };
+        Ok(SensorPort::BrickPi3(BrickPi3Port::new(layer, port)?))
+    }
+
     /// Try to format a device name path to a  port name.
     pub fn format_name(name: &str) -> String {
         match name {
@@ -64,8 +123,63 @@ impl Port for SensorPort {
             SensorPort::In2 => "in2".to_owned(),
             SensorPort::In3 => "in3".to_owned(),
             SensorPort::In4 => "in4".to_owned(),
+            SensorPort::BrickPi3(port) => format!("S{}", port.index() + 1),
         }
     }
+
+    fn layer(&self) -> Option<u8> {
+        match self {
+            SensorPort::BrickPi3(port) => Some(port.layer()),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for SensorPort {
+    type Err = Ev3Error;
+
+    /// Parses an ev3dev port address like `ev3-ports:in3` or a port name like `in3`.
+    /// BrickPi3 addresses like `spi0.1:S5` are parsed to `SensorPort::BrickPi3` with the layer of the port.
+    ///
+    /// Returns `Ev3Error::InternalError` if the address does not belong to any port.
+    ///
+    /// # Example
+    /// ```
+    /// use ev3dev_lang_rust::sensors::SensorPort;
+    /// use ev3dev_lang_rust::{BrickPi3Port, Port};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// assert_eq!("ev3-ports:in3".parse::<SensorPort>()?, SensorPort::In3);
+    /// assert_eq!(SensorPort::In3.address().parse::<SensorPort>()?, SensorPort::In3);
+    /// assert_eq!(
+    ///     "spi0.1:S5".parse::<SensorPort>()?,
+    ///     SensorPort::BrickPi3(BrickPi3Port::new(2, 1)?)
+    /// );
+    /// assert!("spi0.1:S17".parse::<SensorPort>().is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        address
+            .split(':')
+            .filter_map(|part| match part {
+                "in1" => Some(SensorPort::In1),
+                "in2" => Some(SensorPort::In2),
+                "in3" => Some(SensorPort::In3),
+                "in4" => Some(SensorPort::In4),
+                _ => parse_brickpi3_port(part),
+            })
+            .next()
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("`{}` is not a valid SensorPort address", address),
+            })
+    }
+}
+
+/// Parses a BrickPi3 input port like `S5` to its layer and the port on the layer.
+fn parse_brickpi3_port(part: &str) -> Option<SensorPort> {
+    let number: u32 = part.strip_prefix('S')?.parse().ok()?;
+    BrickPi3Port::from_index(number.checked_sub(1)?).map(SensorPort::BrickPi3)
 }
 
 #[macro_export]
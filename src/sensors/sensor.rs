@@ -1,25 +1,170 @@
 //! Common utility functions for sensors.
 
-use crate::{Device, Ev3Result};
+use std::sync::RwLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{BinDataFormat, BinDataType};
+use crate::{Attribute, Device, Ev3Error, Ev3Result};
+
+/// Maximal time to wait for a mode switch to take effect.
+const MODE_SWITCH_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Additional delay after a mode switch, see `set_mode_settle_delay`.
+static MODE_SETTLE_DELAY: RwLock<Duration> = RwLock::new(Duration::ZERO);
+
+/// Value range of a sensor mode set by `Sensor::set_value_range`: address, mode and `(min, max)`.
+type ValueRange = (String, String, (f32, f32));
+
+/// Value ranges set by `Sensor::set_value_range`.
+static VALUE_RANGES: RwLock<Vec<ValueRange>> = RwLock::new(Vec::new());
+
+/// Default value ranges of `Sensor::get_normalized_value` by driver name and mode.
+///
+/// The ranges are the scaled value ranges (see `Sensor::get_float_value`) of `value0` listed in the
+/// ev3dev sensor documentation (<https://docs.ev3dev.org/projects/lego-linux-drivers/en/ev3dev-stretch/sensor_data.html>).
+const DEFAULT_VALUE_RANGES: &[(&str, &str, (f32, f32))] = &[
+    ("lego-ev3-color", "COL-REFLECT", (0.0, 100.0)),
+    ("lego-ev3-color", "COL-AMBIENT", (0.0, 100.0)),
+    ("lego-ev3-color", "RGB-RAW", (0.0, 1020.0)),
+    ("lego-ev3-ir", "IR-PROX", (0.0, 100.0)),
+    ("lego-ev3-touch", "TOUCH", (0.0, 1.0)),
+    ("lego-ev3-us", "US-DIST-CM", (0.0, 255.0)),
+    ("lego-ev3-us", "US-DIST-IN", (0.0, 100.0)),
+    ("lego-nxt-light", "REFLECT", (0.0, 100.0)),
+    ("lego-nxt-light", "AMBIENT", (0.0, 100.0)),
+    ("lego-nxt-sound", "DB", (0.0, 100.0)),
+    ("lego-nxt-sound", "DBA", (0.0, 100.0)),
+    ("lego-nxt-touch", "TOUCH", (0.0, 1.0)),
+    ("lego-nxt-us", "NXT-US-CM", (0.0, 255.0)),
+    ("lego-nxt-us", "NXT-US-IN", (0.0, 100.0)),
+];
+
+/// Minimal polling period of `Sensor::set_poll_ms` in milliseconds, except 0 to disable polling.
+pub const MIN_POLL_MS: i32 = 50;
+
+/// Returns the `poll_ms` attribute, which only exists for I2C and NXT sensors.
+fn poll_ms_attribute<S>(sensor: &S) -> Ev3Result<Attribute>
+where
+    S: Sensor + ?Sized,
+{
+    sensor
+        .try_get_attribute("poll_ms")
+        .map_err(|_| Ev3Error::InternalError {
+            msg: "The sensor has no poll_ms attribute".to_owned(),
+        })
+}
+
+/// Sets an additional delay after the mode switches of the typed sensor getters, zero by default.
+///
+/// Getters like `ColorSensor::get_rgb` switch the mode if necessary and wait until the `mode` attribute
+/// reports the new mode. Some sensors still return values of the previous mode for a few milliseconds
+/// after that, the settle delay is waited before the first value is read.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{self, ColorSensor};
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// sensors::set_mode_settle_delay(Duration::from_millis(10));
+///
+/// let sensor = ColorSensor::find()?;
+/// let (red, green, blue) = sensor.get_rgb()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn set_mode_settle_delay(delay: Duration) {
+    *MODE_SETTLE_DELAY.write().unwrap() = delay;
+}
+
+/// Returns the additional delay after mode switches, see `set_mode_settle_delay`.
+pub fn get_mode_settle_delay() -> Duration {
+    *MODE_SETTLE_DELAY.read().unwrap()
+}
+
+/// Switches the `sensor` to the `mode` if necessary and waits until the switch took effect.
+///
+/// After a switch the `mode` attribute is polled until it reports `mode`, then the settle delay is waited.
+pub(crate) fn switch_mode<S>(sensor: &S, mode: &str) -> Ev3Result<()>
+where
+    S: Sensor + ?Sized,
+{
+    if sensor.ensure_mode(mode)? {
+        wait_mode_applied(sensor, mode)?;
+    }
+    Ok(())
+}
+
+/// Polls the `mode` attribute of the `sensor` until it reports `mode` and waits the settle delay afterwards.
+pub(crate) fn wait_mode_applied<S>(sensor: &S, mode: &str) -> Ev3Result<()>
+where
+    S: Sensor + ?Sized,
+{
+    let start = Instant::now();
+    while sensor.get_mode()? != mode {
+        if start.elapsed() >= MODE_SWITCH_TIMEOUT {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Sensor did not switch to mode `{}`", mode),
+            });
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let delay = get_mode_settle_delay();
+    if delay > Duration::ZERO {
+        thread::sleep(delay);
+    }
+    Ok(())
+}
 
 /// Common utility functions for sensors.
 pub trait Sensor: Device {
-    /// Reading the file will give the unscaled raw values in the `value<N>` attributes.
-    /// Use `bin_data_format`, `num_values` and the individual sensor documentation to determine how to interpret the data.
-    fn get_bin_data(&self) -> Ev3Result<String> {
-        self.get_attribute("bin_data").get()
-    }
-
-    /// Returns the format of the values in `bin_data` for the current mode. Possible values are:
-    // * u8: Unsigned 8-bit integer (byte)
-    // * s8: Signed 8-bit integer (sbyte)
-    // * u16: Unsigned 16-bit integer (ushort)
-    // * s16: Signed 16-bit integer (short)
-    // * s16_be: Signed 16-bit integer, big endian
-    // * s32: Signed 32-bit integer (int)
-    // * s32_be: Signed 32-bit integer, big endian
-    // * float: IEEE 754 32-bit floating point (float)
-    fn get_bin_data_format(&self) -> Ev3Result<String> {
+    /// Returns the raw bytes of the `bin_data` attribute, the unscaled values of the `value<N>` attributes.
+    /// Use `get_bin_data_format`, `num_values` and the individual sensor documentation to determine how to interpret the data,
+    /// or decode it with `get_bin_data_typed`.
+    fn get_bin_data(&self) -> Ev3Result<Vec<u8>> {
+        self.get_attribute("bin_data").get_bytes()
+    }
+
+    /// Decodes the first `num_values` values of `bin_data` with the `bin_data_format` of the current mode to the type `T`.
+    ///
+    /// This reads the raw values of all `value<N>` attributes at once, e.g. of custom sensors with many values.
+    /// Returns `Ev3Error::InternalError` if a value does not fit into `T`, e.g. a `float` value into `i16`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = ColorSensor::find()?;
+    /// sensor.set_mode_rgb_raw()?;
+    ///
+    /// // The `u16` values red, green and blue.
+    /// let rgb = sensor.get_bin_data_typed::<i32>()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_bin_data_typed<T>(&self) -> Ev3Result<Vec<T>>
+    where
+        T: BinDataType,
+        Self: Sized,
+    {
+        let format = self.get_bin_data_format()?;
+        let mode_writes = self.get_attribute("mode").write_count();
+        let num_values: usize = self.get_attribute("num_values").get_keyed(mode_writes)?;
+        format.decode_all(&self.get_bin_data()?, num_values)
+    }
+
+    /// Writes the raw `bytes` to the `bin_data` attribute.
+    /// Sensors with writable modes, e.g. some I2C sensors, send the data to the sensor.
+    /// Use `get_bin_data_format` and the individual sensor documentation to determine the expected data.
+    fn set_values(&self, bytes: &[u8]) -> Ev3Result<()> {
+        self.get_attribute("bin_data").set_bytes(bytes)
+    }
+
+    /// Returns the format of the values in `bin_data` for the current mode, see `BinDataFormat`.
+    fn get_bin_data_format(&self) -> Ev3Result<BinDataFormat> {
         self.get_attribute("bin_data_format").get()
     }
 
@@ -28,10 +173,114 @@ pub trait Sensor: Device {
         self.get_attribute("decimals").get()
     }
 
-    /// Returns the firmware version of the sensor if available.
-    /// Currently only NXT/I2C sensors support this.
-    fn get_fw_version(&self) -> Ev3Result<String> {
-        self.get_attribute("fw_version").get()
+    /// Returns the `value{index}` value scaled by the `decimals` of the current mode,
+    /// e.g. `25.3` for `value0=253` and `decimals=1`.
+    ///
+    /// The `decimals` are cached until the mode is changed by `set_mode` or `set_mode_unchecked` of this sensor.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{Sensor, UltrasonicSensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = UltrasonicSensor::find()?;
+    /// sensor.set_mode_us_dist_cm()?;
+    ///
+    /// // The distance in `US-DIST-CM` mode has one decimal.
+    /// println!("Distance: {} cm", sensor.get_float_value(0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_float_value(&self, index: u8) -> Ev3Result<f32> {
+        let value = self.get_value(index)?;
+        let mode_writes = self.get_attribute("mode").write_count();
+        let decimals: i32 = self.get_attribute("decimals").get_keyed(mode_writes)?;
+        Ok(value as f32 / 10f32.powi(decimals))
+    }
+
+    /// Returns the `value{index}` value of the current mode, normalized to the range 0.0-1.0
+    /// by the value range of the mode, see `get_value_range`.
+    /// Values outside of the range are clamped.
+    ///
+    /// Returns `Ev3Error::InternalError` if no value range is known for the current mode.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = ColorSensor::find()?;
+    /// sensor.set_mode_col_reflect()?;
+    ///
+    /// // Calibrate the reflected light between the black line and the white floor.
+    /// sensor.set_value_range(8.0, 64.0)?;
+    ///
+    /// println!("Brightness: {:.2}", sensor.get_normalized_value(0)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_normalized_value(&self, index: u8) -> Ev3Result<f32> {
+        let (min, max) = match self.get_value_range()? {
+            Some(range) => range,
+            None => {
+                return Err(Ev3Error::InternalError {
+                    msg: format!("No value range known for mode `{}`", self.get_mode()?),
+                })
+            }
+        };
+
+        let value = self.get_float_value(index)?;
+        Ok(((value - min) / (max - min)).clamp(0.0, 1.0))
+    }
+
+    /// Returns the value range `(min, max)` of the current mode used by `get_normalized_value`.
+    ///
+    /// A range set by `set_value_range` takes precedence, otherwise the default range of known sensor modes is used,
+    /// e.g. 0-100 for the `COL-REFLECT` mode of the color sensor. The defaults are the value ranges of the
+    /// ev3dev sensor documentation. Returns `None` if no range is known.
+    fn get_value_range(&self) -> Ev3Result<Option<(f32, f32)>> {
+        let address = self.get_address()?;
+        let mode = self.get_mode()?;
+
+        let custom = VALUE_RANGES
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(a, m, _)| *a == address && *m == mode)
+            .map(|&(_, _, range)| range);
+        if custom.is_some() {
+            return Ok(custom);
+        }
+
+        let driver_name = self.get_driver_name()?;
+        Ok(DEFAULT_VALUE_RANGES
+            .iter()
+            .find(|(d, m, _)| *d == driver_name && *m == mode)
+            .map(|&(_, _, range)| range))
+    }
+
+    /// Sets the value range of the current mode of this sensor for `get_normalized_value`.
+    ///
+    /// The range is stored by the address of the sensor and the mode, so it is shared by all instances for that sensor.
+    /// Returns `Ev3Error::InternalError` if `min` is not smaller than `max`.
+    fn set_value_range(&self, min: f32, max: f32) -> Ev3Result<()> {
+        if min >= max {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Invalid value range {} - {}", min, max),
+            });
+        }
+        let address = self.get_address()?;
+        let mode = self.get_mode()?;
+
+        let mut ranges = VALUE_RANGES.write().unwrap();
+        match ranges
+            .iter_mut()
+            .find(|(a, m, _)| *a == address && *m == mode)
+        {
+            Some(entry) => entry.2 = (min, max),
+            None => ranges.push((address, mode, (min, max))),
+        }
+        Ok(())
     }
 
     /// Returns the current mode.
@@ -42,7 +291,53 @@ pub trait Sensor: Device {
 
     /// Sets the sensor to that mode.
     /// See the individual sensor documentation for a description of the modes available for each type of sensor.
+    ///
+    /// Returns `Ev3Error::InternalError` if the mode is not listed in `get_modes()`.
+    /// Use `set_mode_unchecked()` to skip this validation.
     fn set_mode(&self, mode: &str) -> Ev3Result<()> {
+        // The supported modes are constant per driver, so the list is only read once.
+        let modes: String = self.get_attribute("modes").get_keyed(0)?;
+        let modes: Vec<&str> = modes.split_whitespace().collect();
+        if !modes.contains(&mode) {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Sensor mode `{}` is not supported, use one of {:?}",
+                    mode, modes
+                ),
+            });
+        }
+        self.set_mode_unchecked(mode)
+    }
+
+    /// Sets the sensor to that mode if it is in a different mode, see `set_mode()`.
+    /// Mode switches are slow and can reset the readings, so this should be preferred in loops.
+    ///
+    /// Returns `true` if the mode was switched.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{Sensor, UltrasonicSensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = UltrasonicSensor::find()?;
+    ///
+    /// loop {
+    ///     sensor.ensure_mode(UltrasonicSensor::MODE_US_DIST_CM)?;
+    ///     println!("Distance: {}", sensor.get_value0()?);
+    /// }
+    /// # }
+    /// ```
+    fn ensure_mode(&self, mode: &str) -> Ev3Result<bool> {
+        if self.get_mode()? == mode {
+            return Ok(false);
+        }
+        self.set_mode(mode)?;
+        Ok(true)
+    }
+
+    /// Sets the sensor to that mode without checking it against the list of supported modes.
+    /// This can be used to force undocumented modes.
+    fn set_mode_unchecked(&self, mode: &str) -> Ev3Result<()> {
         self.get_attribute("mode").set_str_slice(mode)
     }
 
@@ -51,25 +346,75 @@ pub trait Sensor: Device {
         self.get_attribute("modes").get_vec()
     }
 
+    /// Sends a sensor specific command to the sensor.
+    ///
+    /// Returns `Ev3Error::InternalError` if the command is not listed in `get_commands()`.
+    /// Use `Device::set_command()` to skip this validation.
+    fn send_command(&self, command: &str) -> Ev3Result<()> {
+        let commands = self.get_commands()?;
+        if !commands.iter().any(|c| c == command) {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Sensor command `{}` is not supported, use one of {:?}",
+                    command, commands
+                ),
+            });
+        }
+        self.set_command(command)
+    }
+
     /// Returns the number of `value<N>` attributes that will return a valid value for the current mode.
     fn get_num_values(&self) -> Ev3Result<i32> {
         self.get_attribute("num_values").get()
     }
 
     /// Returns the polling period of the sensor in milliseconds.
-    /// Returns `-EOPNOTSUPP` if changing polling is not supported.
     /// Note: Setting poll_ms too high can cause the input port autodetection to fail.
     /// If this happens, use the mode attribute of the port to force the port to `nxt-i2c mode`. Values must not be negative.
+    ///
+    /// Returns `Ev3Error::InternalError` if the sensor has no `poll_ms` attribute, e.g. for analog and EV3 UART sensors.
     fn get_poll_ms(&self) -> Ev3Result<i32> {
-        self.get_attribute("poll_ms").get()
+        poll_ms_attribute(self)?.get()
     }
 
     /// Sets the polling period of the sensor in milliseconds.
-    /// Setting to 0 disables polling.
+    /// Setting to 0 disables polling, otherwise the kernel requires at least `MIN_POLL_MS` (50) milliseconds.
+    /// Lower values give faster updates of I2C sensors at the cost of CPU time.
     /// Note: Setting poll_ms too high can cause the input port autodetection to fail.
     /// If this happens, use the mode attribute of the port to force the port to `nxt-i2c mode`. Values must not be negative.
+    ///
+    /// Returns `Ev3Error::InternalError` if the value is out of range, if the sensor has no `poll_ms` attribute
+    /// or if the driver does not support changing the polling period (`EOPNOTSUPP`).
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{CompassSensor, Sensor, MIN_POLL_MS};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = CompassSensor::find()?;
+    ///
+    /// sensor.set_poll_ms(MIN_POLL_MS)?;
+    /// # Ok(())
+    /// # }
+    /// ```
     fn set_poll_ms(&self, poll_ms: i32) -> Ev3Result<()> {
-        self.get_attribute("poll_ms").set(poll_ms)
+        if poll_ms < 0 || (poll_ms > 0 && poll_ms < MIN_POLL_MS) {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Invalid poll_ms {}, use 0 or at least {}",
+                    poll_ms, MIN_POLL_MS
+                ),
+            });
+        }
+
+        match poll_ms_attribute(self)?.set(poll_ms) {
+            Err(ref e) if e.raw_os_error() == Some(libc::EOPNOTSUPP) => {
+                Err(Ev3Error::InternalError {
+                    msg: "The sensor driver does not support changing poll_ms".to_owned(),
+                })
+            }
+            result => result,
+        }
     }
 
     /// Returns the units of the measured value for the current mode. May return empty string if units are unknown.
@@ -77,9 +422,99 @@ pub trait Sensor: Device {
         self.get_attribute("units").get()
     }
 
+    /// Sets the minimal interval between two reads of the `value<N>` attributes.
+    /// Calls of `get_value0` etc. within this interval after the last read return the cached value,
+    /// so fast control loops do not read the sensor more often than it updates (see `get_poll_ms`).
+    /// A zero interval, the default, reads the sensor on every call.
+    ///
+    /// The cache is per attribute and shared with clones of this sensor.
+    /// Value attributes that the sensor does not provide are skipped.
+    fn set_min_poll_interval(&self, interval: Duration) {
+        for index in 0..8 {
+            if let Ok(value) = self.try_get_attribute(&format!("value{}", index)) {
+                value.set_min_poll_interval(interval);
+            }
+        }
+    }
+
+    /// Returns the point in time of the last real read of any `value<N>` attribute,
+    /// `None` if no value was read yet.
+    fn last_read_instant(&self) -> Option<Instant> {
+        (0..8)
+            .filter_map(|index| {
+                self.try_get_attribute(&format!("value{}", index))
+                    .ok()?
+                    .last_read_instant()
+            })
+            .max()
+    }
+
+    /// Returns the values `value0` to `value{num_values - 1}` of the current mode.
+    ///
+    /// The `num_values` are cached until the mode is changed by `set_mode` or `set_mode_unchecked` of this sensor.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = ColorSensor::find()?;
+    /// sensor.set_mode_rgb_raw()?;
+    ///
+    /// // Red, green and blue
+    /// let rgb = sensor.get_all_values()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_all_values(&self) -> Ev3Result<Vec<i32>> {
+        let mode_writes = self.get_attribute("mode").write_count();
+        let num_values: u8 = self.get_attribute("num_values").get_keyed(mode_writes)?;
+        (0..num_values).map(|index| self.get_value(index)).collect()
+    }
+
+    /// Returns an endless iterator that reads all values of the current mode every `interval`, see `SampleIter`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{Sensor, UltrasonicSensor};
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = UltrasonicSensor::find()?;
+    ///
+    /// // Log 100 samples at 20 Hz as CSV.
+    /// for values in sensor.sample_every(Duration::from_millis(50)).take(100) {
+    ///     let values: Vec<_> = values?.iter().map(|value| value.to_string()).collect();
+    ///     println!("{}", values.join(","));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn sample_every(&self, interval: Duration) -> SampleIter<'_, Self>
+    where
+        Self: Sized,
+    {
+        SampleIter {
+            sensor: self,
+            interval,
+            next: Instant::now(),
+        }
+    }
+
+    /// Reads the mode and values of the sensor once, see `SensorSnapshot`.
+    fn snapshot(&self) -> Ev3Result<SensorSnapshot> {
+        Ok(SensorSnapshot {
+            driver_name: self.get_driver_name()?,
+            address: self.get_address()?,
+            mode: self.get_mode()?,
+            values: self.get_all_values()?,
+            decimals: self.get_decimals()?,
+            units: self.get_units()?,
+        })
+    }
+
     /// Returns the current `value{index}` value if available.
     fn get_value(&self, index: u8) -> Ev3Result<i32> {
-        use crate::Ev3Error;
         match index {
             0 => self.get_value0(),
             1 => self.get_value1(),
@@ -95,6 +530,40 @@ pub trait Sensor: Device {
         }
     }
 
+    /// Returns the current `value{index}` value like `get_value`,
+    /// but checks the `index` against the `num_values` of the current mode first.
+    ///
+    /// The `num_values` are cached until the mode is changed by `set_mode` or `set_mode_unchecked` of this sensor.
+    /// Returns `Ev3Error::InternalError` if the current mode has no value with this `index`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{InfraredSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = InfraredSensor::find()?;
+    /// sensor.set_mode_ir_prox()?;
+    ///
+    /// let proximity = sensor.get_value_checked(0)?;
+    /// // `IR-PROX` has a single value.
+    /// assert!(sensor.get_value_checked(1).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn get_value_checked(&self, index: u8) -> Ev3Result<i32> {
+        let mode_writes = self.get_attribute("mode").write_count();
+        let num_values: u8 = self.get_attribute("num_values").get_keyed(mode_writes)?;
+        if index >= num_values {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Sensor value index {} is out of bounds, the current mode has {} values",
+                    index, num_values
+                ),
+            });
+        }
+        self.get_value(index)
+    }
+
     /// Returns the current `value0` value if available.
     fn get_value0(&self) -> Ev3Result<i32> {
         self.get_attribute("value0").get()
@@ -140,3 +609,77 @@ pub trait Sensor: Device {
         self.get_attribute("text_value").get()
     }
 }
+
+/// Endless iterator over the values of a sensor at a fixed cadence, returned by `Sensor::sample_every`.
+///
+/// Each item holds the values of `Sensor::get_all_values`. The first sample is read immediately,
+/// the following samples at multiples of the interval after it, measured with the monotonic `Instant` clock.
+/// The time to read a sample is subtracted from the sleep, so the cadence does not drift.
+/// If reading takes longer than the interval, the missed samples are skipped.
+///
+/// A failed read is yielded as an error item, the iterator continues with the next sample.
+#[derive(Debug)]
+pub struct SampleIter<'a, S: 'a> {
+    sensor: &'a S,
+    interval: Duration,
+    /// Instant of the next sample.
+    next: Instant,
+}
+
+impl<'a, S: Sensor> Iterator for SampleIter<'a, S> {
+    type Item = Ev3Result<Vec<i32>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let now = Instant::now();
+        if self.next > now {
+            thread::sleep(self.next - now);
+        }
+
+        let values = self.sensor.get_all_values();
+
+        let now = Instant::now();
+        if self.interval.is_zero() {
+            self.next = now;
+        } else {
+            self.next += self.interval;
+            while self.next <= now {
+                self.next += self.interval;
+            }
+        }
+
+        Some(values)
+    }
+}
+
+/// Mode and values of a sensor, read once by `Sensor::snapshot`.
+///
+/// With the `serde` feature the snapshot implements `Serialize` and `Deserialize`.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = ColorSensor::find()?;
+///
+/// let snapshot = sensor.snapshot()?;
+/// println!("Mode {}: {:?}", snapshot.mode, snapshot.values);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SensorSnapshot {
+    /// Name of the driver, e.g. `lego-ev3-color`.
+    pub driver_name: String,
+    /// Address of the port, e.g. `ev3-ports:in1`.
+    pub address: String,
+    /// Current mode, e.g. `COL-REFLECT`.
+    pub mode: String,
+    /// Values `value0` to `value{num_values - 1}` of the mode, see `Sensor::get_all_values`.
+    pub values: Vec<i32>,
+    /// Number of decimal places of the values, see `Sensor::get_float_value`.
+    pub decimals: i32,
+    /// Units of the values, may be empty.
+    pub units: String,
+}
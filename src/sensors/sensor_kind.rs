@@ -0,0 +1,85 @@
+//! Auto detection of the sensor type connected to a port.
+
+use super::{
+    ColorSensor, CompassSensor, GyroSensor, InfraredSensor, IrSeekerSensor, LightSensor,
+    SensorPort, SoundSensor, TemperatureSensor, TouchSensor, UltrasonicSensor,
+};
+use crate::{utils::DRIVER_NAME_ATTRIBUTE, Attribute, Driver, Ev3Result};
+
+/// Typed sensor that is connected to a port, see `SensorKind::get`.
+#[derive(Debug, Clone)]
+pub enum SensorKind {
+    /// LEGO EV3 color sensor.
+    Color(ColorSensor),
+    /// HiTechnic EV3 / NXT compass sensor.
+    Compass(CompassSensor),
+    /// LEGO EV3 gyro sensor.
+    Gyro(GyroSensor),
+    /// LEGO EV3 infrared sensor.
+    Infrared(InfraredSensor),
+    /// HiTechnic EV3 / NXT infrared seeker sensor.
+    IrSeeker(IrSeekerSensor),
+    /// LEGO NXT light sensor.
+    Light(LightSensor),
+    /// LEGO NXT sound sensor.
+    Sound(SoundSensor),
+    /// LEGO NXT / EV3 temperature sensor.
+    Temperature(TemperatureSensor),
+    /// LEGO EV3 / NXT touch sensor.
+    Touch(TouchSensor),
+    /// LEGO EV3 / NXT ultrasonic sensor.
+    Ultrasonic(UltrasonicSensor),
+    /// Sensor with a driver that has no typed representation, contains the driver name.
+    Unknown(String),
+}
+
+impl SensorKind {
+    /// Detects the sensor on the given `port` by its `driver_name` attribute
+    /// and returns it wrapped in the matching variant.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no sensor is connected to the `port`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{SensorKind, SensorPort};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// match SensorKind::get(SensorPort::In1)? {
+    ///     SensorKind::Touch(sensor) => println!("Touch sensor pressed: {}", sensor.get_pressed_state()?),
+    ///     SensorKind::Unknown(driver_name) => println!("Unknown sensor: {}", driver_name),
+    ///     sensor => println!("Found {:?}", sensor),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(port: SensorPort) -> Ev3Result<SensorKind> {
+        let name = Driver::find_name_by_port("lego-sensor", &port)?;
+        let driver_name = Attribute::from_sys_class("lego-sensor", &name, DRIVER_NAME_ATTRIBUTE)?
+            .get::<String>()?;
+        let driver_name = driver_name.as_str();
+
+        if ColorSensor::DRIVER_NAMES.contains(&driver_name) {
+            ColorSensor::get(port).map(SensorKind::Color)
+        } else if CompassSensor::DRIVER_NAMES.contains(&driver_name) {
+            CompassSensor::get(port).map(SensorKind::Compass)
+        } else if GyroSensor::DRIVER_NAMES.contains(&driver_name) {
+            GyroSensor::get(port).map(SensorKind::Gyro)
+        } else if InfraredSensor::DRIVER_NAMES.contains(&driver_name) {
+            InfraredSensor::get(port).map(SensorKind::Infrared)
+        } else if IrSeekerSensor::DRIVER_NAMES.contains(&driver_name) {
+            IrSeekerSensor::get(port).map(SensorKind::IrSeeker)
+        } else if LightSensor::DRIVER_NAMES.contains(&driver_name) {
+            LightSensor::get(port).map(SensorKind::Light)
+        } else if SoundSensor::DRIVER_NAMES.contains(&driver_name) {
+            SoundSensor::get(port).map(SensorKind::Sound)
+        } else if TemperatureSensor::DRIVER_NAMES.contains(&driver_name) {
+            TemperatureSensor::get(port).map(SensorKind::Temperature)
+        } else if TouchSensor::DRIVER_NAMES.contains(&driver_name) {
+            TouchSensor::get(port).map(SensorKind::Touch)
+        } else if UltrasonicSensor::DRIVER_NAMES.contains(&driver_name) {
+            UltrasonicSensor::get(port).map(SensorKind::Ultrasonic)
+        } else {
+            Ok(SensorKind::Unknown(driver_name.to_owned()))
+        }
+    }
+}
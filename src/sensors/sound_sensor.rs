@@ -0,0 +1,61 @@
+//! LEGO NXT sound sensor.
+
+use super::{sensor::switch_mode, Sensor, SensorPort};
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+/// LEGO NXT sound sensor.
+///
+/// The raw values of both modes are in the range 0-1000 with one decimal place,
+/// `get_sound_pressure` and `get_sound_pressure_low` scale them to 0.0-100.0 percent.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct SoundSensor {
+    driver: Driver,
+}
+
+impl SoundSensor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["lego-nxt-sound"],
+        SensorPort,
+        "SoundSensor",
+        "in"
+    );
+
+    sensor_mode!(
+        "DB",
+        MODE_DB,
+        "Sound pressure level, flat weighting",
+        set_mode_db,
+        is_mode_db
+    );
+    sensor_mode!(
+        "DBA",
+        MODE_DBA,
+        "Sound pressure level, A weighting",
+        set_mode_dba,
+        is_mode_dba
+    );
+
+    /// A measurement of the measured sound pressure level, as a percentage in the range 0.0-100.0.
+    ///
+    /// Switches the sensor to `DB` mode if necessary and waits until the mode switch took effect.
+    pub fn get_sound_pressure(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_DB)?;
+
+        self.get_float_value(0)
+    }
+
+    /// A measurement of the measured sound pressure level with A weighting, as a percentage in the range 0.0-100.0.
+    /// The A weighting emphasizes the frequencies the human ear is most sensitive to.
+    ///
+    /// Switches the sensor to `DBA` mode if necessary and waits until the mode switch took effect.
+    pub fn get_sound_pressure_low(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_DBA)?;
+
+        self.get_float_value(0)
+    }
+}
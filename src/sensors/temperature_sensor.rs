@@ -0,0 +1,67 @@
+//! LEGO NXT / EV3 temperature sensor.
+
+use super::{sensor::switch_mode, Sensor, SensorPort};
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+/// LEGO NXT / EV3 temperature sensor.
+///
+/// The raw value is in tenths of a degree and negative below zero.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::TemperatureSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = TemperatureSensor::find()?;
+///
+/// println!("Temperature: {:.1} °C", sensor.get_temperature_celsius()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct TemperatureSensor {
+    driver: Driver,
+}
+
+impl TemperatureSensor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["lego-nxt-temp"],
+        SensorPort,
+        "TemperatureSensor",
+        "in"
+    );
+
+    sensor_mode!(
+        "NXT-TEMP-C",
+        MODE_NXT_TEMP_C,
+        "Temperature in degrees Celsius",
+        set_mode_nxt_temp_c,
+        is_mode_nxt_temp_c
+    );
+    sensor_mode!(
+        "NXT-TEMP-F",
+        MODE_NXT_TEMP_F,
+        "Temperature in degrees Fahrenheit",
+        set_mode_nxt_temp_f,
+        is_mode_nxt_temp_f
+    );
+
+    /// Returns the temperature in degrees Celsius.
+    ///
+    /// Switches the sensor to `NXT-TEMP-C` mode if necessary and waits until the mode switch took effect.
+    pub fn get_temperature_celsius(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_NXT_TEMP_C)?;
+
+        self.get_float_value(0)
+    }
+
+    /// Returns the temperature in degrees Fahrenheit, converted from `get_temperature_celsius`.
+    pub fn get_temperature_fahrenheit(&self) -> Ev3Result<f32> {
+        Ok(self.get_temperature_celsius()? * 9.0 / 5.0 + 32.0)
+    }
+}
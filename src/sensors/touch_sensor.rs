@@ -1,7 +1,11 @@
 //! Touch Sensor
 
 use super::{Sensor, SensorPort};
+#[cfg(target_os = "linux")]
+use crate::wait;
 use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant};
 
 /// Touch Sensor
 #[derive(Debug, Clone, Device, Sensor)]
@@ -29,4 +33,72 @@ impl TouchSensor {
     pub fn get_pressed_state(&self) -> Ev3Result<bool> {
         Ok(self.get_value0()? != 0)
     }
+
+    /// Wait until the sensor is pressed or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// Returns immediately if the sensor is already pressed.
+    ///
+    /// Returns `false` if the `timeout` was reached.
+    #[cfg(target_os = "linux")]
+    pub fn wait_for_press(&self, timeout: Option<Duration>) -> Ev3Result<bool> {
+        self.wait_for_state(true, timeout)
+    }
+
+    /// Wait until the sensor is released or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// Returns immediately if the sensor is not pressed.
+    ///
+    /// Returns `false` if the `timeout` was reached.
+    #[cfg(target_os = "linux")]
+    pub fn wait_for_release(&self, timeout: Option<Duration>) -> Ev3Result<bool> {
+        self.wait_for_state(false, timeout)
+    }
+
+    /// Wait until the sensor is pressed and released again or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    ///
+    /// Returns `false` if the `timeout` was reached.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::TouchSensor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = TouchSensor::find()?;
+    ///
+    /// println!("Press the touch sensor to continue");
+    /// sensor.wait_for_bump(None)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub fn wait_for_bump(&self, timeout: Option<Duration>) -> Ev3Result<bool> {
+        let start = Instant::now();
+        if !self.wait_for_press(timeout)? {
+            return Ok(false);
+        }
+
+        let remaining =
+            timeout.map(|timeout| timeout.checked_sub(start.elapsed()).unwrap_or_default());
+        self.wait_for_release(remaining)
+    }
+
+    /// Wait until the pressed state equals `pressed` or the `timeout` is reached.
+    #[cfg(target_os = "linux")]
+    fn wait_for_state(&self, pressed: bool, timeout: Option<Duration>) -> Ev3Result<bool> {
+        let value0 = self.get_attribute("value0");
+        let cond = || {
+            value0
+                .get::<i32>()
+                .map(|value| (value != 0) == pressed)
+                .unwrap_or(false)
+        };
+
+        if wait::wait(value0.get_raw_fd(), cond, timeout) {
+            return Ok(true);
+        }
+        // Report read errors instead of a timeout.
+        self.get_pressed_state()?;
+        Ok(false)
+    }
 }
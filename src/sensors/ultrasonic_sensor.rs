@@ -1,11 +1,16 @@
 //! LEGO EV3 ultrasonic sensor
 
-use super::{Sensor, SensorPort};
-use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+use super::{sensor::switch_mode, Sensor, SensorPort};
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result, Port};
 use std::cell::Cell;
+use std::thread;
+use std::time::Duration;
+
+/// Time the sensor needs to take a single shot measurement after the mode was set.
+const SINGLE_SHOT_DELAY: Duration = Duration::from_millis(50);
 
 /// LEGO EV3 ultrasonic sensor.
-#[derive(Debug, Clone, Device, Sensor)]
+#[derive(Debug, Clone, Sensor)]
 pub struct UltrasonicSensor {
     driver: Driver,
     cm_scale: Cell<Option<f32>>,
@@ -84,24 +89,32 @@ impl UltrasonicSensor {
         self.get_value0()
     }
 
-    /// Measurement of the distance detected by the sensor, in centimeters.
-    pub fn get_distance_centimeters(&self) -> Ev3Result<f32> {
-        let scale_field = self.cm_scale.get();
-        let scale = match scale_field {
-            Some(s) => s,
+    /// Returns the scale of `value0` for centimeter modes.
+    fn get_cm_scale(&self) -> Ev3Result<f32> {
+        match self.cm_scale.get() {
+            Some(s) => Ok(s),
             None => {
                 let decimals = self.get_decimals()?;
                 let s = 10f32.powi(-decimals);
                 self.cm_scale.set(Some(s));
-                s
+                Ok(s)
             }
-        };
-
-        Ok((self.get_value0()? as f32) * scale)
+        }
     }
 
     /// Measurement of the distance detected by the sensor, in centimeters.
+    /// Switches to the continuous `US-DIST-CM` mode if necessary.
+    pub fn get_distance_centimeters(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_US_DIST_CM)?;
+
+        Ok((self.get_value0()? as f32) * self.get_cm_scale()?)
+    }
+
+    /// Measurement of the distance detected by the sensor, in inches.
+    /// Switches to the continuous `US-DIST-IN` mode if necessary.
     pub fn get_distance_inches(&self) -> Ev3Result<f32> {
+        switch_mode(self, Self::MODE_US_DIST_IN)?;
+
         let scale_field = self.in_scale.get();
         let scale = match scale_field {
             Some(s) => s,
@@ -115,4 +128,56 @@ impl UltrasonicSensor {
 
         Ok((self.get_value0()? as f32) * scale)
     }
+
+    /// Takes a single measurement of the distance, in centimeters.
+    ///
+    /// In contrast to the continuous modes the sensor only sends one ping, which does not interfere
+    /// with other ultrasonic sensors nearby. Every call sets the `US-SI-CM` mode again,
+    /// which triggers a new measurement, and waits 50 milliseconds for its result.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::UltrasonicSensor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = UltrasonicSensor::find()?;
+    ///
+    /// println!("Distance: {}cm", sensor.get_distance_single_shot_cm()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_distance_single_shot_cm(&self) -> Ev3Result<f32> {
+        self.set_mode_us_si_cm()?;
+        thread::sleep(SINGLE_SHOT_DELAY);
+
+        Ok((self.get_value0()? as f32) * self.get_cm_scale()?)
+    }
+
+    /// Returns `true` if another ultrasonic sensor is pinging nearby.
+    /// Switches to the `US-LISTEN` mode if necessary.
+    pub fn get_other_sensor_present(&self) -> Ev3Result<bool> {
+        switch_mode(self, Self::MODE_US_LISTEN)?;
+
+        Ok(self.get_value0()? != 0)
+    }
+}
+
+impl Device for UltrasonicSensor {
+    fn get_attribute(&self, name: &str) -> Attribute {
+        self.driver.get_attribute(name)
+    }
+
+    fn try_get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        self.driver.try_get_attribute(name)
+    }
+
+    /// Re-resolves the sensor at the `port` and drops the cached scales of the old device.
+    fn reconnect(&mut self, port: &dyn Port) -> Ev3Result<()> {
+        self.driver
+            .reconnect(port, Self::DRIVER_NAMES)
+            .map_err(Self::map_error)?;
+        self.cm_scale.set(None);
+        self.in_scale.set(None);
+        Ok(())
+    }
 }
@@ -23,7 +23,36 @@
 
 use crate::{Ev3Error, Ev3Result};
 use std::ffi::OsStr;
+use std::io;
+use std::path::Path;
 use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// Spawns the `command`.
+/// Returns a `Ev3Error::InternalError` naming the program if it is not installed.
+fn spawn(command: &mut Command) -> Ev3Result<Child> {
+    command.spawn().map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Ev3Error::InternalError {
+            msg: format!(
+                "`{}` could not be found, make sure it is installed!",
+                command.get_program().to_string_lossy()
+            ),
+        },
+        _ => e.into(),
+    })
+}
+
+/// Waits for the `child` process and returns a `Ev3Error::InternalError` if it failed.
+fn wait_success(mut child: Child) -> Ev3Result<()> {
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Ev3Error::InternalError {
+            msg: format!("Sound process exited with {}", status),
+        })
+    }
+}
 
 /// Call beep command.
 ///
@@ -38,9 +67,7 @@ use std::process::{Child, Command, Stdio};
 /// # }
 /// ```
 pub fn beep() -> Ev3Result<Child> {
-    Ok(Command::new("/usr/bin/beep")
-        .stdout(Stdio::null())
-        .spawn()?)
+    spawn(Command::new("/usr/bin/beep").stdout(Stdio::null()))
 }
 
 /// Call beep command with the provided arguments.
@@ -64,10 +91,11 @@ where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
 {
-    Ok(Command::new("/usr/bin/beep")
-        .args(args)
-        .stdout(Stdio::null())
-        .spawn()?)
+    spawn(
+        Command::new("/usr/bin/beep")
+            .args(args)
+            .stdout(Stdio::null()),
+    )
 }
 
 /// Play tone sequence. The tone_sequence parameter is a list of tuples,
@@ -92,6 +120,24 @@ pub fn tone(frequency: f32, duration: i32) -> Ev3Result<Child> {
     ])
 }
 
+/// Play a tone with the `frequency` in Hz for the given `duration`
+/// and block until the tone has finished.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+/// use std::time::Duration;
+///
+/// # fn main() -> Ev3Result<()> {
+/// sound::tone_blocking(466.0, Duration::from_millis(500))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn tone_blocking(frequency: f32, duration: Duration) -> Ev3Result<()> {
+    wait_success(tone(frequency, duration.as_millis() as i32)?)
+}
+
 /// Play tone sequence. The tone_sequence parameter is a list of tuples,
 /// where each tuple contains up to three numbers. The first number is
 /// frequency in Hz, the second is duration in milliseconds, and the third
@@ -146,27 +192,76 @@ pub fn tone_sequence(sequence: &[(f32, i32, i32)]) -> Ev3Result<Child> {
 
 /// Play wav file
 pub fn play(wav_file: &str) -> Ev3Result<Child> {
-    Ok(Command::new("/usr/bin/aplay")
-        .arg("-q")
-        .arg(wav_file)
-        .stdout(Stdio::null())
-        .spawn()?)
+    play_file(Path::new(wav_file))
+}
+
+/// Play wav file at the given `path`.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+/// use std::path::Path;
+///
+/// # fn main() -> Ev3Result<()> {
+/// sound::play_file(Path::new("/home/robot/bark.wav"))?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn play_file(path: &Path) -> Ev3Result<Child> {
+    spawn(
+        Command::new("/usr/bin/aplay")
+            .arg("-q")
+            .arg(path)
+            .stdout(Stdio::null()),
+    )
 }
 
 /// Speak the given text aloud.
+///
+/// Uses the default english voice with 130 words per minute.
+/// See `speak_with_options` to tune the output.
 pub fn speak(text: &str) -> Ev3Result<Child> {
-    let espeak = Command::new("/usr/bin/espeak")
-        .args(&["--stdout", "-a", "200", "-s", "130", text])
-        .stdout(Stdio::piped())
-        .spawn()?;
+    speak_with_options(text, 130, "en", 50)
+}
 
-    Ok(Command::new("/usr/bin/aplay")
-        .arg("-q")
-        .stdin(espeak.stdout.ok_or(Ev3Error::InternalError {
-            msg: "`espeak` pipe to `aplay` could not be created!".to_owned(),
-        })?)
-        .stdout(Stdio::null())
-        .spawn()?)
+/// Speak the given text aloud with `espeak` using the given `voice` (e.g. `"en"` or `"de"`),
+/// speed in words per minute and `pitch` in the range [0-99].
+///
+/// The text is passed to `espeak` as a single argument without a shell,
+/// so special characters are spoken and not interpreted.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// // Speak slowly with a low voice and wait for completion:
+/// sound::speak_with_options("Hello, I am Robot", 100, "en", 20)?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn speak_with_options(text: &str, speed_wpm: u32, voice: &str, pitch: u8) -> Ev3Result<Child> {
+    let espeak = spawn(
+        Command::new("/usr/bin/espeak")
+            .args(["--stdout", "-a", "200"])
+            .args(["-s", &speed_wpm.to_string()])
+            .args(["-v", voice])
+            .args(["-p", &pitch.min(99).to_string()])
+            // Prevent texts starting with `-` from being parsed as options.
+            .args(["--", text])
+            .stdout(Stdio::piped()),
+    )?;
+
+    spawn(
+        Command::new("/usr/bin/aplay")
+            .arg("-q")
+            .stdin(espeak.stdout.ok_or(Ev3Error::InternalError {
+                msg: "`espeak` pipe to `aplay` could not be created!".to_owned(),
+            })?)
+            .stdout(Stdio::null()),
+    )
 }
 
 /// Get the main channel name or 'Playback' if not available.
@@ -202,11 +297,12 @@ fn get_channels() -> Ev3Result<Vec<String>> {
 /// Sets the sound volume to the given percentage [0-100] by calling
 /// `amixer -q set <channel> <pct>%`.
 pub fn set_volume_channel(volume: i32, channel: &str) -> Ev3Result<()> {
-    Command::new("/usr/bin/amixer")
-        .args(&["-q", "set", channel, &format!("{}%", volume)])
-        .stdout(Stdio::null())
-        .spawn()?
-        .wait()?;
+    spawn(
+        Command::new("/usr/bin/amixer")
+            .args(["-q", "set", channel, &format!("{}%", volume)])
+            .stdout(Stdio::null()),
+    )?
+    .wait()?;
 
     Ok(())
 }
@@ -228,7 +324,7 @@ pub fn set_volume(volume: i32) -> Ev3Result<()> {
 pub fn get_volume_channel(channel: &str) -> Ev3Result<i32> {
     let out = String::from_utf8(
         Command::new("/usr/bin/amixer")
-            .args(&["get", channel])
+            .args(["get", channel])
             .output()?
             .stdout,
     )?;
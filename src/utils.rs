@@ -1,5 +1,67 @@
 //! Utility things.
 
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::sync::{OnceLock, RwLock};
+
+/// The default root driver path `/sys/class/`.
+const DEFAULT_ROOT_PATH: &str = "/sys/class/";
+
+/// Environment variable to override the root driver path.
+const ROOT_PATH_ENV: &str = "EV3DEV_SYS_ROOT";
+
+/// Name of the attribute that contains the port address of a device.
+///
+/// The attribute names of the device lookup are defined here, so they can be swapped for platforms
+/// with a different sysfs layout. The defaults match the ev3dev-stretch kernels for all supported bricks.
+pub(crate) const ADDRESS_ATTRIBUTE: &str = "address";
+
+/// Name of the attribute that contains the driver name of a device.
+pub(crate) const DRIVER_NAME_ATTRIBUTE: &str = "driver_name";
+
+/// Root driver path set by `Driver::set_root_path`.
+static ROOT_PATH_OVERRIDE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Root driver path of the `EV3DEV_SYS_ROOT` environment variable, read on first use.
+static ROOT_PATH_FROM_ENV: OnceLock<Option<String>> = OnceLock::new();
+
+/// Appends a trailing `/` to the `path` if it is missing.
+fn with_trailing_slash(path: &str) -> String {
+    if path.ends_with('/') {
+        path.to_owned()
+    } else {
+        format!("{}/", path)
+    }
+}
+
+/// Sets the root driver path override, `None` restores the default lookup.
+pub(crate) fn set_root_path(path: Option<&str>) {
+    *ROOT_PATH_OVERRIDE.write().unwrap() = path.map(with_trailing_slash);
+}
+
+/// Returns the root driver path with a trailing `/`.
+///
+/// The first of the following is used:
+/// 1. The path set by `Driver::set_root_path`
+/// 2. The `EV3DEV_SYS_ROOT` environment variable, read once on first use
+/// 3. `/sys/class/`
+pub(crate) fn root_path() -> String {
+    if let Some(ref path) = *ROOT_PATH_OVERRIDE.read().unwrap() {
+        return path.clone();
+    }
+
+    ROOT_PATH_FROM_ENV
+        .get_or_init(|| {
+            env::var(ROOT_PATH_ENV)
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(|path| with_trailing_slash(&path))
+        })
+        .clone()
+        .unwrap_or_else(|| DEFAULT_ROOT_PATH.to_owned())
+}
+
 /// Helper `Result` type for easy access.
 pub type Ev3Result<T> = Result<T, Ev3Error>;
 
@@ -24,12 +86,95 @@ pub enum Ev3Error {
         device: String,
         /// Devices of the requested type were found on this ports.
         ports: Vec<String>,
+        /// Sysfs names of the matching devices (e.g. `sensor0`), in the same order as `ports`.
+        names: Vec<String>,
     },
+    /// A wait operation reached its timeout before the awaited condition became true.
+    Timeout,
+    /// The operating system reported an error, e.g. while reading an attribute file.
+    Os {
+        /// Original io error, carrying the errno.
+        error: std::io::Error,
+    },
+}
+
+impl Ev3Error {
+    /// Returns the errno of an `Ev3Error::Os` error, `None` for all other errors.
+    /// This mirrors `std::io::Error::raw_os_error`.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::motors::LargeMotor;
+    ///
+    /// match LargeMotor::find().and_then(|motor| motor.get_position()) {
+    ///     Ok(position) => println!("Position: {}", position),
+    ///     // ENODEV, the motor was unplugged.
+    ///     Err(ref e) if e.raw_os_error() == Some(19) => println!("Motor disconnected"),
+    ///     Err(e) => println!("Error: {:?}", e),
+    /// }
+    /// ```
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match *self {
+            Ev3Error::Os { ref error } => error.raw_os_error(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Ev3Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Ev3Error::InternalError { ref msg } => write!(f, "{}", msg),
+            Ev3Error::NotConnected {
+                ref device,
+                port: Some(ref port),
+            } => write!(f, "No `{}` found on port `{}`", device, port),
+            Ev3Error::NotConnected {
+                ref device,
+                port: None,
+            } => {
+                write!(f, "No `{}` found", device)
+            }
+            Ev3Error::MultipleMatches {
+                ref device,
+                ref ports,
+                ref names,
+            } => {
+                write!(f, "Multiple `{}` found:", device)?;
+                for (index, port) in ports.iter().enumerate() {
+                    match names.get(index) {
+                        Some(name) => write!(f, " `{}` on port `{}`", name, port)?,
+                        None => write!(f, " port `{}`", port)?,
+                    }
+                    if index + 1 < ports.len() {
+                        write!(f, ",")?;
+                    }
+                }
+                Ok(())
+            }
+            Ev3Error::Timeout => write!(f, "Timeout reached"),
+            Ev3Error::Os { ref error } => write!(f, "{}", error),
+        }
+    }
 }
+
+impl Error for Ev3Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            Ev3Error::Os { ref error } => Some(error),
+            _ => None,
+        }
+    }
+}
+
 impl From<std::io::Error> for Ev3Error {
     fn from(err: std::io::Error) -> Self {
-        Ev3Error::InternalError {
-            msg: format!("{}", err),
+        if err.raw_os_error().is_some() {
+            Ev3Error::Os { error: err }
+        } else {
+            Ev3Error::InternalError {
+                msg: format!("{}", err),
+            }
         }
     }
 }
@@ -63,6 +208,66 @@ impl From<framebuffer::FramebufferError> for Ev3Error {
 pub trait Port {
     /// Returns the name of the port.
     fn address(&self) -> String;
+
+    /// Returns the layer of a port on stacked boards like the BrickPi3, starting at `1`.
+    /// Returns `None` for ports of platforms without layers, e.g. the EV3 ports.
+    fn layer(&self) -> Option<u8> {
+        None
+    }
+}
+
+/// Number of BrickPi3 boards that can be stacked, see `BrickPi3Port`.
+pub const BRICKPI3_MAX_LAYERS: u8 = 4;
+
+/// A port of a board in a stack of BrickPi3 boards, see `SensorPort::BrickPi3` and `MotorPort::BrickPi3`.
+///
+/// The `layer` is `1` to `BRICKPI3_MAX_LAYERS` and the `port` on the board is `1` to `4`,
+/// so every value belongs to an existing port.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BrickPi3Port {
+    layer: u8,
+    port: u8,
+}
+
+impl BrickPi3Port {
+    /// Returns the `port` (`1` to `4`) of the board at the `layer` (`1` to `BRICKPI3_MAX_LAYERS`).
+    ///
+    /// Returns `Ev3Error::InternalError` if the layer or the port is out of range.
+    pub fn new(layer: u8, port: u8) -> Ev3Result<BrickPi3Port> {
+        if !(1..=BRICKPI3_MAX_LAYERS).contains(&layer) || !(1..=4).contains(&port) {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Invalid BrickPi3 port {} on layer {}", port, layer),
+            });
+        }
+        Ok(BrickPi3Port { layer, port })
+    }
+
+    /// Returns the layer of the board in the stack, starting at `1`.
+    pub fn layer(self) -> u8 {
+        self.layer
+    }
+
+    /// Returns the port on the board, starting at `1`.
+    pub fn port(self) -> u8 {
+        self.port
+    }
+
+    /// Returns the index of the port over all layers, starting at `0`.
+    /// The ports of all layers are numbered consecutively.
+    pub(crate) fn index(self) -> u8 {
+        (self.layer - 1) * 4 + self.port - 1
+    }
+
+    /// Returns the port with the `index` over all layers, `None` if there is no such port.
+    pub(crate) fn from_index(index: u32) -> Option<BrickPi3Port> {
+        if index >= u32::from(BRICKPI3_MAX_LAYERS) * 4 {
+            return None;
+        }
+        Some(BrickPi3Port {
+            layer: (index / 4 + 1) as u8,
+            port: (index % 4 + 1) as u8,
+        })
+    }
 }
 
 /// Helper trait to convert an option to an error.
@@ -2,12 +2,168 @@
 //! Uses the `libc::epoll_wait` that only works on linux systems.
 
 use libc;
-use std::os::unix::io::RawFd;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "tokio")]
+use std::future::Future;
+#[cfg(feature = "tokio")]
+use std::pin::Pin;
+#[cfg(feature = "tokio")]
+use std::task::{Context, Poll};
+
+#[cfg(feature = "tokio")]
+use tokio::io::unix::AsyncFd;
+#[cfg(feature = "tokio")]
+use tokio::time::Sleep;
+
+use crate::{Attribute, Ev3Error, Ev3Result};
+
+/// Sleep interval if no `epoll` instance could be created.
+const FALLBACK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Default of `set_max_poll_interval`.
+pub const DEFAULT_MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Interval to re-check the condition without file events, see `set_max_poll_interval`.
+static MAX_POLL_INTERVAL: RwLock<Option<Duration>> = RwLock::new(Some(DEFAULT_MAX_POLL_INTERVAL));
+
+/// Sets the maximal interval the wait functions sleep without checking their condition,
+/// `DEFAULT_MAX_POLL_INTERVAL` (50ms) by default.
+///
+/// Not every sensor driver signals changes of its `value<N>` attributes with `epoll` events.
+/// Without events the condition would never be checked again, so the wait functions additionally
+/// re-check it after this interval. `None` relies on file events only.
+///
+/// # Example
+/// ```
+/// use ev3dev_lang_rust::wait;
+/// use std::time::Duration;
+///
+/// wait::set_max_poll_interval(Some(Duration::from_millis(20)));
+/// # wait::set_max_poll_interval(Some(wait::DEFAULT_MAX_POLL_INTERVAL));
+/// ```
+pub fn set_max_poll_interval(interval: Option<Duration>) {
+    *MAX_POLL_INTERVAL.write().unwrap() = interval;
+}
+
+/// Returns the maximal interval the wait functions sleep without checking their condition.
+pub fn get_max_poll_interval() -> Option<Duration> {
+    *MAX_POLL_INTERVAL.read().unwrap()
+}
+
+/// Owned `epoll` instance that listens to changes of sysfs attribute files.
+///
+/// Sysfs signals changes of an attribute with `EPOLLPRI | EPOLLERR`.
+/// The file descriptors are registered edge-triggered, so every change is reported once.
+/// The registered file descriptors are not owned by the `Epoll` instance and must be kept open
+/// while it is in use. The `epoll` file descriptor itself is closed on drop.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Attribute;
+/// use ev3dev_lang_rust::wait::Epoll;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let state = Attribute::from_sys_class("tacho-motor", "motor0", "state")?;
+/// let epoll = Epoll::new(state.get_raw_fd())?;
+///
+/// if epoll.wait(Some(Duration::from_secs(1))) {
+///     println!("Motor state changed: {}", state.get::<String>()?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Epoll {
+    fd: RawFd,
+}
+
+impl Epoll {
+    /// Create a new `epoll` instance that listens to changes of the file `fd`.
+    pub fn new(fd: RawFd) -> Ev3Result<Epoll> {
+        let epoll = Epoll::create()?;
+        epoll.add(fd)?;
+        Ok(epoll)
+    }
+
+    /// Create a new `epoll` instance that listens to new events of the input device `fd`,
+    /// e.g. `/dev/input/by-path/platform-gpio_keys-event`.
+    pub fn new_input(fd: RawFd) -> Ev3Result<Epoll> {
+        let epoll = Epoll::create()?;
+        epoll.add_input(fd)?;
+        Ok(epoll)
+    }
+
+    /// Create a new `epoll` instance without any registered file.
+    pub(crate) fn create() -> Ev3Result<Epoll> {
+        let epoll_fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+        if epoll_fd < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(Epoll { fd: epoll_fd })
+    }
+
+    /// Listen to changes of the additional file `fd`.
+    pub fn add(&self, fd: RawFd) -> Ev3Result<()> {
+        self.register(fd, libc::EPOLLPRI | libc::EPOLLERR | libc::EPOLLET)
+    }
+
+    /// Listen to new events of the additional input device `fd`.
+    /// Input devices signal new events with `EPOLLIN` instead of `EPOLLPRI`.
+    pub fn add_input(&self, fd: RawFd) -> Ev3Result<()> {
+        self.register(fd, libc::EPOLLIN | libc::EPOLLET)
+    }
+
+    /// Wrapper for `libc::epoll_ctl`
+    fn register(&self, fd: RawFd, events: i32) -> Ev3Result<()> {
+        let mut event = libc::epoll_event {
+            events: events as u32,
+            u64: fd as u64,
+        };
+
+        let result = unsafe { libc::epoll_ctl(self.fd, libc::EPOLL_CTL_ADD, fd, &mut event) };
+        if result < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    /// Wait until one of the files has changed or the `timeout` is reached.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    /// Returns `true` if a change was reported.
+    pub fn wait(&self, timeout: Option<Duration>) -> bool {
+        let timeout = match timeout {
+            Some(duration) => duration.as_millis() as i32,
+            None => -1,
+        };
+        wait_file_changes(self.fd, timeout)
+    }
+}
+
+impl AsRawFd for Epoll {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for Epoll {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
 /// Wait for until a condition `cond` is `true` or the `timeout` is reached.
 /// If the `timeout` is `None` it will wait an infinite time.
-/// The condition is checked when the `file` has changed.
+/// The condition is checked when the `file` has changed and at least every `get_max_poll_interval()`,
+/// because not every driver reports changes with `epoll` events.
 ///
 /// # Arguments
 /// * `file` - Listen to changes in this file
@@ -40,16 +196,94 @@ where
         return true;
     }
 
+    wait_epoll(Epoll::new(fd).ok(), cond, timeout)
+}
+
+/// Wait for until a condition `cond` is `true` or the `timeout` is reached.
+/// If the `timeout` is `None` it will wait an infinite time.
+/// The condition is checked when any of the files `fds` has changed.
+///
+/// All files are registered in a single `epoll` instance, so no thread per file is required.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{Sensor, TouchSensor, UltrasonicSensor};
+/// use ev3dev_lang_rust::wait;
+/// use ev3dev_lang_rust::Device;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let touch = TouchSensor::find()?;
+/// let ultrasonic = UltrasonicSensor::find()?;
+///
+/// let fds = [
+///     touch.get_attribute("value0").get_raw_fd(),
+///     ultrasonic.get_attribute("value0").get_raw_fd(),
+/// ];
+/// let cond = || {
+///     touch.get_pressed_state().unwrap_or(false)
+///         || ultrasonic.get_distance_centimeters().map(|d| d < 10.0).unwrap_or(false)
+/// };
+///
+/// wait::wait_any(&fds, cond, Some(Duration::from_secs(10)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn wait_any<F>(fds: &[RawFd], cond: F, timeout: Option<Duration>) -> bool
+where
+    F: Fn() -> bool,
+{
+    if cond() {
+        return true;
+    }
+
+    let epoll = Epoll::create().and_then(|epoll| {
+        for fd in fds {
+            epoll.add(*fd)?;
+        }
+        Ok(epoll)
+    });
+    wait_epoll(epoll.ok(), cond, timeout)
+}
+
+/// Wait for until a condition `cond` is `true` or the `timeout` is reached.
+/// If the `timeout` is `None` it will wait an infinite time.
+/// The condition is checked when the input device `fd` reports a new event.
+///
+/// The events are not consumed, so `cond` can query the device state with an ioctl.
+pub fn wait_input<F>(fd: RawFd, cond: F, timeout: Option<Duration>) -> bool
+where
+    F: Fn() -> bool,
+{
+    if cond() {
+        return true;
+    }
+
+    wait_epoll(Epoll::new_input(fd).ok(), cond, timeout)
+}
+
+/// Wait loop of `wait`, `wait_any` and `wait_input`.
+/// Falls back to sleeping if no `epoll` instance is available.
+fn wait_epoll<F>(epoll: Option<Epoll>, cond: F, timeout: Option<Duration>) -> bool
+where
+    F: Fn() -> bool,
+{
     let start = Instant::now();
+    let max_poll_interval = get_max_poll_interval();
 
     let mut t = timeout;
 
     loop {
-        let wait_timeout = match t {
-            Some(duration) => duration.as_millis() as i32,
-            None => -1,
-        };
-        wait_file_changes(fd, wait_timeout);
+        match epoll {
+            Some(ref epoll) => {
+                let t = match (t, max_poll_interval) {
+                    (Some(t), Some(interval)) => Some(t.min(interval)),
+                    (t, interval) => t.or(interval),
+                };
+                epoll.wait(t);
+            }
+            None => thread::sleep(t.map_or(FALLBACK_INTERVAL, |t| t.min(FALLBACK_INTERVAL))),
+        }
 
         if let Some(duration) = timeout {
             let elapsed = start.elapsed();
@@ -65,18 +299,303 @@ where
     }
 }
 
+/// Wait for until a condition `cond` is `true` or the `timeout` is reached.
+/// If the `timeout` is `None` it will wait an infinite time.
+/// The condition is checked when the `file` has changed.
+///
+/// In contrast to `wait` this returns `Ev3Error::Timeout` if the `timeout` is reached,
+/// so it can be composed with the `?` operator.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Attribute;
+/// use ev3dev_lang_rust::wait;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let state = Attribute::from_sys_class("tacho-motor", "motor0", "state")?;
+/// let cond = || {
+///     state
+///         .get_vec()
+///         .map(|states| !states.iter().any(|s| s == "running"))
+///         .unwrap_or(false)
+/// };
+///
+/// wait::wait_result(state.get_raw_fd(), cond, Some(Duration::from_secs(5)))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn wait_result<F>(fd: RawFd, cond: F, timeout: Option<Duration>) -> Ev3Result<()>
+where
+    F: Fn() -> bool,
+{
+    if wait(fd, cond, timeout) {
+        Ok(())
+    } else {
+        Err(Ev3Error::Timeout)
+    }
+}
+
+/// Returns an iterator that yields every time the file `fd` has changed, until the `timeout` is reached.
+/// If the `timeout` is `None` the iterator never ends.
+///
+/// In contrast to `wait` no condition is checked, so the caller can re-read the file
+/// and react to every intermediate state in its own loop.
+/// The iterator only yields for changes reported by `epoll`. It does not yield after
+/// `get_max_poll_interval()`, so drivers that do not report changes never wake it up.
+///
+/// Returns `Ev3Error::Os` if no `epoll` instance can be created for the file.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Attribute;
+/// use ev3dev_lang_rust::wait;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let state = Attribute::from_sys_class("tacho-motor", "motor0", "state")?;
+///
+/// for () in wait::watch(state.get_raw_fd(), Some(Duration::from_secs(5)))? {
+///     let states = state.get_vec()?;
+///     println!("Motor state changed: {:?}", states);
+///     if states.iter().any(|s| s == "stalled") {
+///         break;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn watch(fd: RawFd, timeout: Option<Duration>) -> Ev3Result<Watch> {
+    Ok(Watch {
+        epoll: Epoll::new(fd)?,
+        deadline: timeout.map(|timeout| Instant::now() + timeout),
+    })
+}
+
+/// Iterator returned by `watch`, that yields every time the watched file has changed.
+#[derive(Debug)]
+pub struct Watch {
+    epoll: Epoll,
+    deadline: Option<Instant>,
+}
+
+impl Iterator for Watch {
+    type Item = ();
+
+    fn next(&mut self) -> Option<()> {
+        loop {
+            let timeout = match self.deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    if remaining == Duration::ZERO {
+                        return None;
+                    }
+                    Some(remaining)
+                }
+                None => None,
+            };
+
+            if self.epoll.wait(timeout) {
+                return Some(());
+            }
+        }
+    }
+}
+
+/// Background thread that calls a closure whenever the parsed value of an attribute changes,
+/// returned by `Attribute::on_change`.
+///
+/// The thread waits for `epoll` events of the attribute file and re-reads the value
+/// at least every `get_max_poll_interval()`, like `wait`. Values that can not be parsed are skipped.
+/// The thread exits if the attribute can not be read anymore, e.g. because the device was disconnected.
+///
+/// Dropping the subscription stops the thread and waits until it has finished.
+#[derive(Debug)]
+#[must_use = "the subscription is cancelled when it is dropped"]
+pub struct Subscription {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Subscription {
+    /// Spawns the thread that calls `f` with every new value of the `attribute`.
+    pub(crate) fn spawn<T, F>(attribute: Attribute, mut f: F) -> Subscription
+    where
+        T: std::str::FromStr + PartialEq + Clone + Send + 'static,
+        <T as std::str::FromStr>::Err: std::error::Error,
+        F: FnMut(T) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+
+        let thread = thread::spawn(move || {
+            let epoll = Epoll::new(attribute.get_raw_fd()).ok();
+            let interval = get_max_poll_interval().unwrap_or(DEFAULT_MAX_POLL_INTERVAL);
+
+            let mut last = match attribute.get_uncached::<T>() {
+                Ok(value) => Some(value),
+                Err(Ev3Error::Os { .. }) => return,
+                Err(_) => None,
+            };
+
+            while !stopped.load(Ordering::SeqCst) {
+                match epoll {
+                    Some(ref epoll) => {
+                        epoll.wait(Some(interval));
+                    }
+                    None => thread::sleep(FALLBACK_INTERVAL),
+                }
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                match attribute.get_uncached::<T>() {
+                    Ok(value) => {
+                        if last.as_ref() != Some(&value) {
+                            last = Some(value.clone());
+                            f(value);
+                        }
+                    }
+                    Err(Ev3Error::Os { .. }) => break,
+                    Err(_) => {}
+                }
+            }
+        });
+
+        Subscription {
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    /// Returns `false` if the thread has exited, e.g. because the device was disconnected.
+    pub fn is_active(&self) -> bool {
+        self.thread
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            // A panic of the callback has already been reported by the thread.
+            thread.join().ok();
+        }
+    }
+}
+
 /// Wrapper for `libc::epoll_wait`
 fn wait_file_changes(fd: RawFd, timeout: i32) -> bool {
     let mut buf: [libc::epoll_event; 10] = [libc::epoll_event { events: 0, u64: 0 }; 10];
 
-    let result = unsafe {
-        libc::epoll_wait(
-            fd,
-            buf.as_mut_ptr() as *mut libc::epoll_event,
-            buf.len() as i32,
-            timeout,
-        ) as i32
-    };
+    let result = unsafe { libc::epoll_wait(fd, buf.as_mut_ptr(), buf.len() as i32, timeout) };
 
     result > 0
 }
+
+/// Future returned by `Attribute::wait_async`.
+///
+/// Resolves to `Ok(())` once the condition is `true` or to `Ev3Error::Timeout` if the timeout is reached.
+/// The attribute file is registered in an `Epoll` instance, whose own file descriptor is driven by the tokio reactor.
+/// The condition is checked every time the attribute file has changed
+/// and at least every `get_max_poll_interval()`.
+///
+/// Must be polled within a tokio runtime.
+#[cfg(feature = "tokio")]
+pub struct WaitFuture<F> {
+    attribute: Attribute,
+    cond: F,
+    timeout: Option<Duration>,
+    epoll: Option<AsyncFd<Epoll>>,
+    sleep: Option<Pin<Box<Sleep>>>,
+    /// Re-checks the condition without file events, see `set_max_poll_interval`.
+    poll_interval: Option<(Duration, Pin<Box<Sleep>>)>,
+}
+
+#[cfg(feature = "tokio")]
+impl<F> WaitFuture<F>
+where
+    F: Fn() -> bool + Unpin,
+{
+    /// Create a new future that waits for `cond` on changes of the `attribute` file.
+    pub(crate) fn new(attribute: Attribute, cond: F, timeout: Option<Duration>) -> Self {
+        WaitFuture {
+            attribute,
+            cond,
+            timeout,
+            epoll: None,
+            sleep: None,
+            poll_interval: None,
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<F> Future for WaitFuture<F>
+where
+    F: Fn() -> bool + Unpin,
+{
+    type Output = Ev3Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.epoll.is_none() {
+            if (this.cond)() {
+                return Poll::Ready(Ok(()));
+            }
+
+            // The `AsyncFd` registration requires a running reactor, so it is deferred to the first poll.
+            let epoll = Epoll::new(this.attribute.get_raw_fd())
+                .and_then(|epoll| AsyncFd::new(epoll).map_err(Ev3Error::from));
+            match epoll {
+                Ok(epoll) => this.epoll = Some(epoll),
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+            this.sleep = this
+                .timeout
+                .map(|timeout| Box::pin(tokio::time::sleep(timeout)));
+            this.poll_interval = get_max_poll_interval()
+                .map(|interval| (interval, Box::pin(tokio::time::sleep(interval))));
+        }
+
+        let epoll = this
+            .epoll
+            .as_ref()
+            .expect("Epoll instance should be registered");
+        loop {
+            match epoll.poll_read_ready(cx) {
+                Poll::Ready(Ok(mut guard)) => {
+                    // Consume the pending events, the attribute file itself is read by `cond`.
+                    guard.get_inner().wait(Some(Duration::from_millis(0)));
+                    guard.clear_ready();
+
+                    if (this.cond)() {
+                        return Poll::Ready(Ok(()));
+                    }
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some((interval, ref mut tick)) = this.poll_interval {
+            while tick.as_mut().poll(cx).is_ready() {
+                if (this.cond)() {
+                    return Poll::Ready(Ok(()));
+                }
+                tick.as_mut().reset(tokio::time::Instant::now() + interval);
+            }
+        }
+
+        if let Some(ref mut sleep) = this.sleep {
+            if sleep.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(Ev3Error::Timeout));
+            }
+        }
+
+        Poll::Pending
+    }
+}